@@ -0,0 +1,163 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+
+use crate::{sha1, HEX_DIGITS};
+
+/// A SHA1 hash
+///
+/// Comparing two hashes with `==` takes the same time regardless of where they differ, so it can
+/// be used to verify secret values. Hashes are formatted as lowercase hexadecimal digits by
+/// [`Display`](fmt::Display) and can be parsed from hexadecimal digits of either case.
+/// ```rust
+/// # use sha1_const::Sha1Digest;
+/// const HASH: Sha1Digest = Sha1Digest::digest(b"this is a test");
+/// assert_eq!(HASH.to_string(), "fa26be19de6bff93f70bc2308434e4a440bbad02");
+/// assert_eq!(format!("{:X}", HASH), "FA26BE19DE6BFF93F70BC2308434E4A440BBAD02");
+/// assert_eq!("fa26be19de6bff93f70bc2308434e4a440bbad02".parse(), Ok(HASH));
+/// ```
+#[derive(Clone, Copy, Eq)]
+pub struct Sha1Digest([u8; 20]);
+
+impl Sha1Digest {
+    /// Wraps the bytes of a SHA1 hash
+    pub const fn from_bytes(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+
+    /// Computes the SHA1 hash of `data`
+    pub const fn digest(data: &[u8]) -> Self {
+        Self(sha1(data))
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    pub const fn into_bytes(self) -> [u8; 20] {
+        self.0
+    }
+
+    fn fmt_hex(&self, f: &mut fmt::Formatter, digits: &[u8; 16]) -> fmt::Result {
+        if f.alternate() {
+            f.write_str("0x")?;
+        }
+
+        for x in self.0 {
+            let hex = [digits[(x >> 4) as usize], digits[(x & 0xf) as usize]];
+            // hexadecimal digits are always ASCII
+            f.write_str(crate::str_from_utf8(&hex))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PartialEq for Sha1Digest {
+    fn eq(&self, other: &Self) -> bool {
+        let mut diff = 0;
+        for (a, b) in self.0.iter().zip(other.0.iter()) {
+            diff |= a ^ b;
+        }
+
+        core::hint::black_box(diff) == 0
+    }
+}
+
+impl Hash for Sha1Digest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl From<[u8; 20]> for Sha1Digest {
+    fn from(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<Sha1Digest> for [u8; 20] {
+    fn from(digest: Sha1Digest) -> Self {
+        digest.0
+    }
+}
+
+impl AsRef<[u8]> for Sha1Digest {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Sha1Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Sha1Digest({})", self)
+    }
+}
+
+impl fmt::Display for Sha1Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(self, f)
+    }
+}
+
+impl fmt::LowerHex for Sha1Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(f, HEX_DIGITS)
+    }
+}
+
+impl fmt::UpperHex for Sha1Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_hex(f, b"0123456789ABCDEF")
+    }
+}
+
+impl FromStr for Sha1Digest {
+    type Err = ParseDigestError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.as_bytes();
+        if s.len() != 40 {
+            return Err(ParseDigestError::InvalidLength(s.len()));
+        }
+
+        let mut bytes = [0; 20];
+        for (i, x) in bytes.iter_mut().enumerate() {
+            *x = (hex_value(s[i * 2])? << 4) | hex_value(s[i * 2 + 1])?;
+        }
+
+        Ok(Self(bytes))
+    }
+}
+
+fn hex_value(digit: u8) -> Result<u8, ParseDigestError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(ParseDigestError::InvalidDigit),
+    }
+}
+
+/// An error returned when parsing a [`Sha1Digest`] fails
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseDigestError {
+    /// The string does not consist of 40 characters
+    InvalidLength(usize),
+    /// The string contains a character which is not a hexadecimal digit
+    InvalidDigit,
+}
+
+impl fmt::Display for ParseDigestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength(x) => {
+                write!(f, "expected 40 hexadecimal digits, found {} bytes", x)
+            }
+            Self::InvalidDigit => f.write_str("invalid hexadecimal digit"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDigestError {}
@@ -0,0 +1,201 @@
+//! Computing SHA1 hashes in `const fn`
+//!
+//! This is a companion to [`sha1-macros`](https://crates.io/crates/sha1-macros) for the cases
+//! where the hashed value is not a literal, such as a `const` item. The hash is computed during
+//! constant evaluation, so it is still embedded into the binary as a constant.
+//!
+//! # Examples
+//! ```rust
+//! # use sha1_const::*;
+//! const NAME: &str = "this is a test";
+//! const HASH: [u8; 20] = sha1(NAME.as_bytes());
+//! const HEX: [u8; 40] = to_hex(&HASH);
+//! const HEX_STR: &str = str_from_utf8(&HEX);
+//! assert_eq!(HEX_STR, "fa26be19de6bff93f70bc2308434e4a440bbad02");
+//!
+//! // or, in a single step
+//! assert_eq!(const_sha1_hex!(NAME.as_bytes()), "fa26be19de6bff93f70bc2308434e4a440bbad02");
+//! ```
+//!
+//! The [`Sha1Digest`] type wraps the bytes of a hash, so it can be told apart from other byte
+//! arrays. The `sha1_digest!` macro of `sha1-macros` produces it directly.
+//!
+//! Note that the compiler limits how long constant evaluation may take, so hashing large inputs
+//! this way may fail to compile. Prefer the macros of `sha1-macros` for literals and files.
+
+#![no_std]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+mod digest;
+
+pub use crate::digest::{ParseDigestError, Sha1Digest};
+
+const H: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Computes the SHA1 hash of `data`
+///
+/// ```rust
+/// # use sha1_const::sha1;
+/// const HASH: [u8; 20] = sha1(b"this is a test");
+/// assert_eq!(HASH[..4], [0xfa, 0x26, 0xbe, 0x19]);
+/// ```
+///
+/// Messages of any length are padded correctly, including ones whose padding does not fit into
+/// their last block, such as the test vectors of FIPS 180:
+/// ```rust
+/// # use sha1_const::const_sha1_hex;
+/// assert_eq!(const_sha1_hex!(b""), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+/// assert_eq!(
+///     const_sha1_hex!(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+///     "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+/// );
+/// assert_eq!(
+///     const_sha1_hex!(b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"),
+///     "a49b2446a02c645bf419f995b67091253a04a259",
+/// );
+/// ```
+pub const fn sha1(data: &[u8]) -> [u8; 20] {
+    // the message is followed by a 0x80 byte and its length in bits as a 64-bit integer, then
+    // padded with zeros to a multiple of the block size
+    let padded_len = (data.len() + 8) / 64 * 64 + 64;
+    let mut h = H;
+
+    let mut block = 0;
+    while block < padded_len {
+        let mut w = [0u32; 80];
+        let mut i = 0;
+        while i < 16 {
+            let offset = block + i * 4;
+            w[i] = u32::from_be_bytes([
+                padded_byte(data, padded_len, offset),
+                padded_byte(data, padded_len, offset + 1),
+                padded_byte(data, padded_len, offset + 2),
+                padded_byte(data, padded_len, offset + 3),
+            ]);
+            i += 1;
+        }
+
+        while i < 80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+            i += 1;
+        }
+
+        h = compress(h, &w);
+        block += 64;
+    }
+
+    let mut hash = [0; 20];
+    let mut i = 0;
+    while i < 20 {
+        hash[i] = (h[i / 4] >> (24 - i % 4 * 8)) as u8;
+        i += 1;
+    }
+
+    hash
+}
+
+/// Returns the byte at `offset` of the padded message
+const fn padded_byte(data: &[u8], padded_len: usize, offset: usize) -> u8 {
+    if offset < data.len() {
+        data[offset]
+    } else if offset == data.len() {
+        0x80
+    } else if offset >= padded_len - 8 {
+        let bits = (data.len() as u64).wrapping_mul(8);
+        bits.to_be_bytes()[offset + 8 - padded_len]
+    } else {
+        0
+    }
+}
+
+const fn compress(h: [u32; 5], w: &[u32; 80]) -> [u32; 5] {
+    let [mut a, mut b, mut c, mut d, mut e] = h;
+    let mut i = 0;
+    while i < 80 {
+        let (f, k) = match i / 20 {
+            0 => ((b & c) | (!b & d), 0x5a827999),
+            1 => (b ^ c ^ d, 0x6ed9eba1),
+            2 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(w[i]);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+        i += 1;
+    }
+
+    [
+        h[0].wrapping_add(a),
+        h[1].wrapping_add(b),
+        h[2].wrapping_add(c),
+        h[3].wrapping_add(d),
+        h[4].wrapping_add(e),
+    ]
+}
+
+/// Encodes a SHA1 hash as lowercase hexadecimal digits
+///
+/// Use [`str_from_utf8`] to turn the result into a `&str`.
+/// ```rust
+/// # use sha1_const::to_hex;
+/// const HEX: [u8; 40] = to_hex(&[0xab; 20]);
+/// assert_eq!(&HEX[..4], b"abab");
+/// ```
+pub const fn to_hex(hash: &[u8; 20]) -> [u8; 40] {
+    let mut hex = [0; 40];
+    let mut i = 0;
+    while i < 20 {
+        hex[i * 2] = HEX_DIGITS[(hash[i] >> 4) as usize];
+        hex[i * 2 + 1] = HEX_DIGITS[(hash[i] & 0xf) as usize];
+        i += 1;
+    }
+
+    hex
+}
+
+/// Converts bytes to a string in `const` context, panicking if they are not valid UTF-8
+///
+/// The output of [`to_hex`] is always valid UTF-8. When used to initialize a `const` item, a panic
+/// results in a compile error.
+/// ```rust
+/// # use sha1_const::str_from_utf8;
+/// const VALUE: &str = str_from_utf8(b"abc");
+/// assert_eq!(VALUE, "abc");
+/// ```
+pub const fn str_from_utf8(bytes: &[u8]) -> &str {
+    match core::str::from_utf8(bytes) {
+        Ok(x) => x,
+        Err(_) => panic!("bytes are not valid UTF-8"),
+    }
+}
+
+/// Computes the SHA1 hash of a constant byte slice as a hexadecimal string
+///
+/// The argument must be usable in `const` context. The resulting value is of type
+/// `&'static str`.
+/// ```rust
+/// # use sha1_const::const_sha1_hex;
+/// const NAME: &str = "this is a test";
+/// const HASH: &str = const_sha1_hex!(NAME.as_bytes());
+/// assert_eq!(HASH, "fa26be19de6bff93f70bc2308434e4a440bbad02");
+/// ```
+#[macro_export]
+macro_rules! const_sha1_hex {
+    ($data:expr) => {{
+        const HEX: [u8; 40] = $crate::to_hex(&$crate::sha1($data));
+        const HEX_STR: &str = $crate::str_from_utf8(&HEX);
+        HEX_STR
+    }};
+}
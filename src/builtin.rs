@@ -0,0 +1,239 @@
+use std::path::PathBuf;
+
+use proc_macro2::Span;
+use syn::parse::{self, Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Lit, LitStr, Macro, Token};
+
+use crate::input::{Dependencies, ToBytes};
+
+const SUPPORTED: &str =
+    "`concat!`, `stringify!`, `env!`, `option_env!`, `include_str!` or `include_bytes!`";
+
+/// A call to a built-in macro of the standard library, evaluated by the proc macro itself
+///
+/// Proc macros receive the tokens of nested macro calls without them being expanded, so the
+/// macros that commonly produce literals are reimplemented here.
+pub enum Builtin {
+    Concat(Vec<ConcatArg>),
+    Stringify(proc_macro2::TokenStream),
+    Env(LitStr),
+    OptionEnv(LitStr),
+    IncludeStr(LitStr),
+    IncludeBytes(LitStr),
+}
+
+impl Builtin {
+    /// Evaluates the macro to a string, as required inside of `concat!`
+    fn to_string(&self, deps: &mut Dependencies) -> syn::Result<String> {
+        match self {
+            Self::Concat(args) => {
+                let mut value = String::new();
+                for arg in args {
+                    value += &arg.to_string(deps)?;
+                }
+
+                Ok(value)
+            }
+            Self::Stringify(tokens) => Ok(tokens.to_string()),
+            Self::Env(name) => {
+                deps.add_env(name.value());
+                std::env::var(name.value()).map_err(|e| {
+                    syn::Error::new(
+                        name.span(),
+                        format!("environment variable `{}` {}", name.value(), env_error(e)),
+                    )
+                })
+            }
+            Self::OptionEnv(name) => Err(syn::Error::new(
+                name.span(),
+                "`option_env!` cannot be concatenated",
+            )),
+            Self::IncludeStr(path) => {
+                let bytes = include(path, deps)?;
+                String::from_utf8(bytes).map_err(|_| {
+                    syn::Error::new(path.span(), format!("{} is not valid UTF-8", path.value()))
+                })
+            }
+            Self::IncludeBytes(path) => Err(syn::Error::new(
+                path.span(),
+                "`include_bytes!` cannot be concatenated",
+            )),
+        }
+    }
+}
+
+impl ToBytes for Builtin {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        match self {
+            Self::OptionEnv(name) => {
+                deps.add_env(name.value());
+                match std::env::var(name.value()) {
+                    Ok(value) => Ok(value.into_bytes()),
+                    Err(std::env::VarError::NotPresent) => Ok(Vec::new()),
+                    Err(e) => Err(syn::Error::new(
+                        name.span(),
+                        format!("environment variable `{}` {}", name.value(), env_error(e)),
+                    )),
+                }
+            }
+            Self::IncludeBytes(path) => include(path, deps),
+            _ => Ok(self.to_string(deps)?.into_bytes()),
+        }
+    }
+}
+
+impl Parse for Builtin {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        let mac: Macro = input.parse()?;
+        let name = match mac.path.segments.len() {
+            1 => &mac.path.segments[0].ident,
+            2 if ["core", "std"]
+                .iter()
+                .any(|x| mac.path.segments[0].ident == x) =>
+            {
+                &mac.path.segments[1].ident
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &mac.path,
+                    format!("expected {}", SUPPORTED),
+                ))
+            }
+        };
+
+        if name == "concat" {
+            let args = mac.parse_body_with(Punctuated::<ConcatArg, Token![,]>::parse_terminated)?;
+            Ok(Self::Concat(args.into_iter().collect()))
+        } else if name == "stringify" {
+            Ok(Self::Stringify(mac.tokens))
+        } else if name == "env" {
+            Ok(Self::Env(mac.parse_body_with(parse_env_args)?))
+        } else if name == "option_env" {
+            Ok(Self::OptionEnv(mac.parse_body_with(parse_single_arg)?))
+        } else if name == "include_str" {
+            Ok(Self::IncludeStr(mac.parse_body_with(parse_single_arg)?))
+        } else if name == "include_bytes" {
+            Ok(Self::IncludeBytes(mac.parse_body_with(parse_single_arg)?))
+        } else {
+            Err(syn::Error::new(
+                name.span(),
+                format!("unsupported macro `{}!`, expected {}", name, SUPPORTED),
+            ))
+        }
+    }
+}
+
+/// An argument of `concat!`
+pub enum ConcatArg {
+    Literal(Option<Token![-]>, Lit),
+    Macro(Builtin),
+}
+
+impl ConcatArg {
+    fn to_string(&self, deps: &mut Dependencies) -> syn::Result<String> {
+        let (minus, value) = match self {
+            Self::Literal(minus, lit) => (minus, lit),
+            Self::Macro(mac) => return mac.to_string(deps),
+        };
+
+        let value = match value {
+            Lit::Str(x) => x.value(),
+            Lit::Char(x) => x.value().to_string(),
+            Lit::Int(x) => x.base10_digits().to_owned(),
+            Lit::Float(x) => x.base10_digits().to_owned(),
+            Lit::Bool(x) => x.value.to_string(),
+            x => {
+                return Err(syn::Error::new(
+                    x.span(),
+                    "cannot concatenate a byte string or byte literal",
+                ))
+            }
+        };
+
+        Ok(match minus {
+            Some(_) => format!("-{}", value),
+            None => value,
+        })
+    }
+}
+
+impl Parse for ConcatArg {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        if input.peek(syn::Ident) || input.peek(Token![::]) {
+            Ok(Self::Macro(input.parse()?))
+        } else {
+            let minus: Option<Token![-]> = input.parse()?;
+            let lit = input.parse()?;
+            if minus.is_some() && !matches!(lit, Lit::Int(_) | Lit::Float(_)) {
+                return Err(syn::Error::new(lit.span(), "expected a number"));
+            }
+
+            Ok(Self::Literal(minus, lit))
+        }
+    }
+}
+
+fn parse_single_arg(input: ParseStream) -> parse::Result<LitStr> {
+    let arg = input.parse()?;
+    input.parse::<Option<Token![,]>>()?;
+    Ok(arg)
+}
+
+/// Parses the arguments of `env!`, ignoring the optional error message
+fn parse_env_args(input: ParseStream) -> parse::Result<LitStr> {
+    let args = Punctuated::<LitStr, Token![,]>::parse_terminated(input)?;
+    match args.len() {
+        1 | 2 => Ok(args[0].clone()),
+        _ => Err(input.error("expected the name of an environment variable")),
+    }
+}
+
+fn env_error(e: std::env::VarError) -> &'static str {
+    match e {
+        std::env::VarError::NotPresent => "is not defined",
+        std::env::VarError::NotUnicode(_) => "is not valid unicode",
+    }
+}
+
+/// Reads a file like `include_bytes!`, relative to the file containing the macro call
+fn include(path: &LitStr, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+    let resolved = resolve(path)?;
+    let bytes = std::fs::read(&resolved).map_err(|e| {
+        syn::Error::new(
+            path.span(),
+            format!("couldn't read {}: {}", resolved.display(), e),
+        )
+    })?;
+
+    deps.add_file(resolved);
+    Ok(bytes)
+}
+
+fn resolve(path: &LitStr) -> syn::Result<PathBuf> {
+    let value = PathBuf::from(path.value());
+    if value.is_absolute() {
+        return Ok(value);
+    }
+
+    let file = span_file(path.span()).ok_or_else(|| {
+        syn::Error::new(
+            path.span(),
+            "cannot resolve a relative path without knowing the invoking file",
+        )
+    })?;
+
+    let dir = file.parent().map(PathBuf::from).unwrap_or_default();
+    Ok(dir.join(value))
+}
+
+fn span_file(span: Span) -> Option<PathBuf> {
+    let file = span.unwrap().local_file()?;
+    if file.is_absolute() {
+        return Some(file);
+    }
+
+    // Relative paths of local files are relative to the working directory of the compiler. They
+    // must be made absolute, as `include_bytes!` would resolve them relative to the invoking file.
+    std::env::current_dir().ok().map(|dir| dir.join(file))
+}
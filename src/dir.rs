@@ -0,0 +1,110 @@
+use std::path::{Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+use syn::LitStr;
+
+use crate::input::{Dependencies, FileInput, ToBytes};
+use crate::options::Options;
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// A directory whose files are hashed, optionally filtered by glob patterns
+///
+/// Every regular file below the directory is visited in order of its `/`-separated path relative
+/// to the directory, compared byte-wise. Each file contributes its relative path, a NUL byte, the
+/// length of its contents as a 64-bit big-endian integer and finally its contents.
+pub struct DirInput<'a> {
+    dir: &'a FileInput,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl<'a> DirInput<'a> {
+    /// Reads the `include` and `exclude` patterns from the options
+    pub fn new(dir: &'a FileInput, options: &mut Options) -> syn::Result<Self> {
+        Ok(DirInput {
+            dir,
+            include: parse_patterns(options.strings("include")?)?,
+            exclude: parse_patterns(options.strings("exclude")?)?,
+        })
+    }
+
+    fn is_included(&self, path: &str) -> bool {
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|x| x.matches_with(path, MATCH_OPTIONS));
+
+        included
+            && !self
+                .exclude
+                .iter()
+                .any(|x| x.matches_with(path, MATCH_OPTIONS))
+    }
+
+    fn walk(
+        &self,
+        dir: &Path,
+        prefix: &str,
+        files: &mut Vec<(String, PathBuf)>,
+    ) -> syn::Result<()> {
+        let entries = std::fs::read_dir(dir).map_err(|e| self.error(dir, e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| self.error(dir, e))?;
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_str().ok_or_else(|| {
+                self.dir
+                    .error(format!("path is not valid UTF-8: {}", path.display()))
+            })?;
+
+            let relative = format!("{}{}", prefix, name);
+            let metadata = std::fs::metadata(&path).map_err(|e| self.error(&path, e))?;
+            if metadata.is_dir() {
+                self.walk(&path, &format!("{}/", relative), files)?;
+            } else if metadata.is_file() && self.is_included(&relative) {
+                files.push((relative, path));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn error(&self, path: &Path, e: std::io::Error) -> syn::Error {
+        self.dir
+            .error(format!("couldn't read {}: {}", path.display(), e))
+    }
+}
+
+impl ToBytes for DirInput<'_> {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        let root = self.dir.resolve()?;
+        let mut files = Vec::new();
+        self.walk(&root, "", &mut files)?;
+        files.sort_unstable_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+
+        let mut bytes = Vec::new();
+        for (relative, path) in files {
+            let contents = std::fs::read(&path).map_err(|e| self.error(&path, e))?;
+            bytes.extend_from_slice(relative.as_bytes());
+            bytes.push(0);
+            bytes.extend_from_slice(&(contents.len() as u64).to_be_bytes());
+            bytes.extend_from_slice(&contents);
+            deps.add_file(path);
+        }
+
+        Ok(bytes)
+    }
+}
+
+fn parse_patterns(patterns: Vec<LitStr>) -> syn::Result<Vec<Pattern>> {
+    patterns
+        .iter()
+        .map(|x| Pattern::new(&x.value()).map_err(|e| syn::Error::new(x.span(), e)))
+        .collect()
+}
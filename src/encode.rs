@@ -0,0 +1,425 @@
+use std::ffi::CString;
+
+use proc_macro::{Literal, Punct, Spacing, TokenStream, TokenTree};
+use proc_macro2::Span;
+use quote::quote;
+
+use crate::options::Options;
+
+/// Turns a hash into the output of a macro, reading the options that apply to it
+pub trait Encoder: FnOnce(&[u8], &mut Options) -> syn::Result<TokenStream> {}
+
+impl<F: FnOnce(&[u8], &mut Options) -> syn::Result<TokenStream>> Encoder for F {}
+
+/// Encodes a hash as hexadecimal digits
+///
+/// Supports `case = lower | upper`, a separator `sep = "..."` between groups and the number of
+/// bytes per group `group = N`, which defaults to 1.
+pub fn encode_hex(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let upper = options.keyword("case", &["lower", "upper"])? == Some("upper");
+    let sep = options.string("sep")?;
+    let group = match options.int("group")? {
+        Some(group) if sep.is_none() => {
+            return Err(syn::Error::new(
+                group.span(),
+                "`group` requires a separator, such as `sep = \" \"`",
+            ))
+        }
+        Some(group) => match group.base10_parse::<usize>()? {
+            0 => return Err(syn::Error::new(group.span(), "`group` must not be 0")),
+            x => x,
+        },
+        None => 1,
+    };
+
+    // `len` counts digits, so the digits are truncated before the separators are inserted
+    let mut digits = hex::encode(hash);
+    digits.truncate(truncated_len(digits.len(), "digits", options)?);
+    if upper {
+        digits.make_ascii_uppercase();
+    }
+
+    let hex = digits
+        .as_bytes()
+        .chunks(group.saturating_mul(2))
+        .map(|x| std::str::from_utf8(x).expect("hex digits should be ASCII"))
+        .collect::<Vec<_>>()
+        .join(&sep.map(|x| x.value()).unwrap_or_default());
+    string_literal(hex, options)
+}
+
+/// Encodes a hash as base64
+///
+/// Supports `alphabet = standard | url_safe | bcrypt | crypt` and the `pad` flag, which appends
+/// `=` padding characters.
+pub fn encode_base64(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    use base64::engine::{GeneralPurpose, GeneralPurposeConfig};
+    use base64::{alphabet, Engine};
+
+    let alphabet =
+        match options.keyword("alphabet", &["standard", "url_safe", "bcrypt", "crypt"])? {
+            Some("url_safe") => &alphabet::URL_SAFE,
+            Some("bcrypt") => &alphabet::BCRYPT,
+            Some("crypt") => &alphabet::CRYPT,
+            _ => &alphabet::STANDARD,
+        };
+    let config = GeneralPurposeConfig::new().with_encode_padding(options.flag("pad")?);
+
+    let hash = GeneralPurpose::new(alphabet, config).encode(hash);
+    string_literal(truncated(hash, options)?, options)
+}
+
+/// Encodes a hash as base32
+///
+/// Supports `alphabet = rfc4648 | crockford`, `case = upper | lower` and the `pad` flag, which
+/// appends `=` padding characters.
+pub fn encode_base32(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let alphabet = match options.keyword("alphabet", &["rfc4648", "crockford"])? {
+        Some("crockford") => CROCKFORD_ALPHABET,
+        _ => BASE32_ALPHABET,
+    };
+    let lower = options.keyword("case", &["upper", "lower"])? == Some("lower");
+    let pad = options.flag("pad")?;
+
+    let mut base32 = String::new();
+    for chunk in hash.chunks(5) {
+        let mut block = [0; 5];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let block = u64::from_be_bytes([0, 0, 0, block[0], block[1], block[2], block[3], block[4]]);
+
+        // every started group of 5 bits is encoded
+        let digits = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < digits {
+                base32.push(alphabet[(block >> (35 - i * 5)) as usize & 0x1f] as char);
+            } else if pad {
+                base32.push('=');
+            }
+        }
+    }
+
+    if lower {
+        base32.make_ascii_lowercase();
+    }
+
+    string_literal(truncated(base32, options)?, options)
+}
+
+/// Encodes a hash as base58 with the alphabet used by Bitcoin
+///
+/// Like in Bitcoin addresses, every leading zero byte is encoded as a `1`.
+pub fn encode_base58(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let zeros = hash.iter().take_while(|x| **x == 0).count();
+    let mut base58 = "1".repeat(zeros);
+    base58.extend(to_radix(&hash[zeros..], 58, 0).map(|x| BASE58_ALPHABET[x] as char));
+    string_literal(truncated(base58, options)?, options)
+}
+
+/// Encodes a hash as a base36 number, padded with zeros to the length required by the largest
+/// hash of its size
+///
+/// Supports `case = lower | upper`.
+pub fn encode_base36(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let upper = options.keyword("case", &["lower", "upper"])? == Some("upper");
+
+    let len = to_radix(&vec![0xff; hash.len()], 36, 0).count();
+    let mut base36: String = to_radix(hash, 36, len)
+        .map(|x| char::from_digit(x as u32, 36).expect("digit should be in range"))
+        .collect();
+    if upper {
+        base36.make_ascii_uppercase();
+    }
+
+    string_literal(truncated(base36, options)?, options)
+}
+
+/// Encodes a hash with the Z85 encoding of ZeroMQ, which requires its length to be a multiple
+/// of 4 bytes
+pub fn encode_z85(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    if !hash.len().is_multiple_of(4) {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!(
+                "Z85 requires a multiple of 4 bytes, but the hash is {} bytes long",
+                hash.len()
+            ),
+        ));
+    }
+
+    let mut z85 = String::new();
+    for chunk in hash.chunks(4) {
+        let value = u32::from_be_bytes(chunk.try_into().expect("chunk should be 4 bytes long"));
+        for i in (0..5).rev() {
+            z85.push(Z85_ALPHABET[(value / 85u32.pow(i) % 85) as usize] as char);
+        }
+    }
+
+    string_literal(z85, options)
+}
+
+/// Encodes a hash with the Bech32 format of BIP 173
+///
+/// Requires the human-readable part `hrp = "..."` and supports `variant = bech32 | bech32m`, which
+/// selects the checksum constant of BIP 350.
+pub fn encode_bech32(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let hrp = options.string("hrp")?.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            "expected the human-readable part as an option, such as `hrp = \"id\"`",
+        )
+    })?;
+    let constant = match options.keyword("variant", &["bech32", "bech32m"])? {
+        Some("bech32m") => 0x2bc830a3,
+        _ => 1,
+    };
+
+    let prefix = hrp.value();
+    if prefix.is_empty()
+        || !prefix
+            .bytes()
+            .all(|x| (33..=126).contains(&x) && !x.is_ascii_uppercase())
+    {
+        return Err(syn::Error::new(
+            hrp.span(),
+            "the human-readable part must consist of ASCII characters 33-126 without uppercase letters",
+        ));
+    }
+
+    let mut data = Vec::new();
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for x in hash {
+        acc = (acc << 8) | *x as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            data.push((acc >> bits) as u8 & 0x1f);
+        }
+    }
+
+    if bits > 0 {
+        data.push((acc << (5 - bits)) as u8 & 0x1f);
+    }
+
+    // the separator, data and checksum must fit into the 90 characters allowed by BIP 173
+    let max_prefix = BECH32_MAX_LEN - 1 - data.len() - 6;
+    if prefix.len() > max_prefix {
+        return Err(syn::Error::new(
+            hrp.span(),
+            format!(
+                "the human-readable part must be at most {} characters long",
+                max_prefix
+            ),
+        ));
+    }
+
+    let mut values: Vec<u8> = prefix.bytes().map(|x| x >> 5).collect();
+    values.push(0);
+    values.extend(prefix.bytes().map(|x| x & 0x1f));
+    values.extend_from_slice(&data);
+    values.extend_from_slice(&[0; 6]);
+    let checksum = bech32_polymod(&values) ^ constant;
+    data.extend((0..6).map(|i| (checksum >> (5 * (5 - i))) as u8 & 0x1f));
+
+    let mut bech32 = format!("{}1", prefix);
+    bech32.extend(data.iter().map(|x| BECH32_ALPHABET[*x as usize] as char));
+    string_literal(bech32, options)
+}
+
+/// Encodes a hash as a byte array, keeping only the first `len = N` bytes if given
+pub fn encode_bytes(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let len = truncated_len(hash.len(), "bytes", options)?;
+    Ok(TokenStream::from_iter([
+        TokenTree::Punct(Punct::new('*', Spacing::Joint)),
+        Literal::byte_string(&hash[..len]).into(),
+    ]))
+}
+
+/// Encodes a UUID in its hyphenated form, such as `cfbff0d1-9375-5685-968c-48ce8b15ae17`
+///
+/// Supports `case = lower | upper`.
+pub fn encode_uuid(uuid: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let upper = options.keyword("case", &["lower", "upper"])? == Some("upper");
+    let mut hyphenated = [
+        &uuid[..4],
+        &uuid[4..6],
+        &uuid[6..8],
+        &uuid[8..10],
+        &uuid[10..],
+    ]
+    .map(hex::encode)
+    .join("-");
+    if upper {
+        hyphenated.make_ascii_uppercase();
+    }
+
+    string_literal(hyphenated, options)
+}
+
+/// Encodes a hash as a strong HTTP entity tag, which consists of hexadecimal digits in quotes
+///
+/// Supports keeping only the first `len = N` digits.
+pub fn encode_etag(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let hex = hex::encode(hash);
+    let len = truncated_len(hex.len(), "digits", options)?;
+    string_literal(format!("\"{}\"", &hex[..len]), options)
+}
+
+/// Encodes the first 4 bytes of a hash as a `u32`
+pub fn encode_u32(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let value = u32::from_be_bytes(int_bytes(hash, options)?);
+    Ok(TokenTree::Literal(Literal::u32_suffixed(value)).into())
+}
+
+/// Encodes the first 8 bytes of a hash as a `u64`
+pub fn encode_u64(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let value = u64::from_be_bytes(int_bytes(hash, options)?);
+    Ok(TokenTree::Literal(Literal::u64_suffixed(value)).into())
+}
+
+/// Encodes the first 16 bytes of a hash as a `u128`
+pub fn encode_u128(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let value = u128::from_be_bytes(int_bytes(hash, options)?);
+    Ok(TokenTree::Literal(Literal::u128_suffixed(value)).into())
+}
+
+/// Encodes a hash as an array of `u32` words, which are big-endian unless `endian = little` is
+/// given
+pub fn encode_words(hash: &[u8], options: &mut Options) -> syn::Result<TokenStream> {
+    let little = options.keyword("endian", &["big", "little"])? == Some("little");
+    let words = hash.chunks_exact(4).map(|x| {
+        let bytes = x.try_into().expect("chunk should be 4 bytes long");
+        let word = if little {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        };
+
+        proc_macro2::Literal::u32_suffixed(word)
+    });
+
+    Ok(quote!([#(#words),*]).into())
+}
+
+/// Returns the first `N` bytes of a hash in big-endian order, reversing them if
+/// `endian = little` is given
+fn int_bytes<const N: usize>(hash: &[u8], options: &mut Options) -> syn::Result<[u8; N]> {
+    let little = options.keyword("endian", &["big", "little"])? == Some("little");
+    let mut bytes: [u8; N] = hash
+        .get(..N)
+        .and_then(|x| x.try_into().ok())
+        .ok_or_else(|| {
+            syn::Error::new(
+                Span::call_site(),
+                format!("the hash is shorter than {} bytes", N),
+            )
+        })?;
+    if little {
+        bytes.reverse();
+    }
+
+    Ok(bytes)
+}
+
+/// Keeps only the first `len = N` characters of an encoded hash if given
+///
+/// Only encodings whose prefixes are still meaningful support this, unlike ones with a checksum
+/// or a fixed group size.
+fn truncated(value: String, options: &mut Options) -> syn::Result<String> {
+    let len = truncated_len(value.chars().count(), "characters", options)?;
+    Ok(value.chars().take(len).collect())
+}
+
+/// Turns the output of a string encoder into a literal
+///
+/// The `cstr` flag produces a C string literal of type `&'static CStr` instead.
+pub fn string_literal(value: String, options: &mut Options) -> syn::Result<TokenStream> {
+    if !options.flag("cstr")? {
+        return Ok(TokenTree::Literal(Literal::string(&value)).into());
+    }
+
+    let value = CString::new(value).map_err(|_| {
+        syn::Error::new(
+            Span::call_site(),
+            "a C string must not contain NUL characters",
+        )
+    })?;
+    Ok(TokenTree::Literal(Literal::c_string(&value)).into())
+}
+
+/// Reads the `len` option, failing if it is 0 or exceeds the length of the output
+fn truncated_len(max: usize, unit: &str, options: &mut Options) -> syn::Result<usize> {
+    truncated_len_or(max, max, unit, options)
+}
+
+/// Reads the `len` option like [`truncated_len`], but returns `default` if it is not given
+pub fn truncated_len_or(
+    default: usize,
+    max: usize,
+    unit: &str,
+    options: &mut Options,
+) -> syn::Result<usize> {
+    let len = match options.int("len")? {
+        Some(len) => len,
+        None => return Ok(default),
+    };
+
+    match len.base10_parse()? {
+        0 => Err(syn::Error::new(len.span(), "`len` must not be 0")),
+        x if x > max => Err(syn::Error::new(
+            len.span(),
+            format!("`len` must be at most {}, the full length in {}", max, unit),
+        )),
+        x => Ok(x),
+    }
+}
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const CROCKFORD_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+const BECH32_ALPHABET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_MAX_LEN: usize = 90;
+
+/// Converts a big-endian number to its digits in `radix`, most significant first, padded with
+/// zeros to at least `len` digits
+fn to_radix(bytes: &[u8], radix: u32, len: usize) -> impl Iterator<Item = usize> {
+    let mut digits = Vec::new();
+    for x in bytes {
+        let mut carry = *x as u32;
+        for digit in &mut digits {
+            carry += *digit * 256;
+            *digit = carry % radix;
+            carry /= radix;
+        }
+
+        while carry > 0 {
+            digits.push(carry % radix);
+            carry /= radix;
+        }
+    }
+
+    if digits.len() < len {
+        digits.resize(len, 0);
+    }
+
+    digits.into_iter().rev().map(|x| x as usize)
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+    let mut checksum = 1u32;
+    for x in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x1ffffff) << 5) ^ *x as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+
+    checksum
+}
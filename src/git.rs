@@ -0,0 +1,152 @@
+use std::path::Path;
+
+use sha1::Digest;
+use syn::parse::{self, Parse, ParseStream};
+
+use crate::input::{Dependencies, FileInput, ToBytes};
+
+/// An input that is turned into a git object, whose ID is the hash of its header and contents
+pub trait GitObject {
+    fn id<D: Digest>(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>>;
+}
+
+/// A blob, whose contents are the bytes of `I`
+pub struct GitBlob<I>(I);
+
+impl<I: ToBytes> GitObject for GitBlob<I> {
+    fn id<D: Digest>(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        Ok(object_id::<D>("blob", &self.0.to_bytes(deps)?))
+    }
+}
+
+impl<I: Parse> Parse for GitBlob<I> {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        Ok(GitBlob(input.parse()?))
+    }
+}
+
+/// A tree of the files below a directory, like git stores it for a commit
+///
+/// Files are stored with mode 100644, or 100755 if they are executable by their owner. Symbolic
+/// links are stored as blobs containing their target. Like in git, directories without any files
+/// are left out and so are `.git` directories.
+pub struct GitTree(FileInput);
+
+impl GitTree {
+    /// Returns the ID of the tree of `dir`, or `None` if it does not contain any files
+    fn tree_id<D: Digest>(
+        &self,
+        dir: &Path,
+        deps: &mut Dependencies,
+    ) -> syn::Result<Option<Vec<u8>>> {
+        let mut entries = Vec::new();
+        let dir_entries = std::fs::read_dir(dir).map_err(|e| self.error(dir, e))?;
+        for entry in dir_entries {
+            let entry = entry.map_err(|e| self.error(dir, e))?;
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_str().ok_or_else(|| {
+                self.0
+                    .error(format!("path is not valid UTF-8: {}", path.display()))
+            })?;
+
+            let metadata = std::fs::symlink_metadata(&path).map_err(|e| self.error(&path, e))?;
+            let (mode, id) = if metadata.is_symlink() {
+                let target = std::fs::read_link(&path).map_err(|e| self.error(&path, e))?;
+                let target = target.to_str().ok_or_else(|| {
+                    self.0.error(format!(
+                        "link target is not valid UTF-8: {}",
+                        path.display()
+                    ))
+                })?;
+                ("120000", object_id::<D>("blob", target.as_bytes()))
+            } else if metadata.is_dir() {
+                if name == ".git" {
+                    continue;
+                }
+
+                match self.tree_id::<D>(&path, deps)? {
+                    Some(id) => ("40000", id),
+                    None => continue,
+                }
+            } else if metadata.is_file() {
+                let contents = std::fs::read(&path).map_err(|e| self.error(&path, e))?;
+                deps.add_file(path);
+
+                let mode = if is_executable(&metadata) {
+                    "100755"
+                } else {
+                    "100644"
+                };
+                (mode, object_id::<D>("blob", &contents))
+            } else {
+                // reading anything else, such as a named pipe, could block forever
+                return Err(self.0.error(format!(
+                    "not a file, directory or symbolic link: {}",
+                    path.display()
+                )));
+            };
+
+            entries.push((name.to_owned(), mode, id));
+        }
+
+        if entries.is_empty() {
+            return Ok(None);
+        }
+
+        // git sorts directories as if their name ended with a slash
+        entries.sort_unstable_by_key(|(name, mode, _)| match *mode {
+            "40000" => format!("{}/", name).into_bytes(),
+            _ => name.clone().into_bytes(),
+        });
+
+        let mut contents = Vec::new();
+        for (name, mode, id) in entries {
+            contents.extend_from_slice(format!("{} {}\0", mode, name).as_bytes());
+            contents.extend_from_slice(&id);
+        }
+
+        Ok(Some(object_id::<D>("tree", &contents)))
+    }
+
+    fn error(&self, path: &Path, e: std::io::Error) -> syn::Error {
+        self.0
+            .error(format!("couldn't read {}: {}", path.display(), e))
+    }
+}
+
+impl GitObject for GitTree {
+    fn id<D: Digest>(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        let root = self.0.resolve()?;
+        // an empty tree is still a valid object
+        Ok(self
+            .tree_id::<D>(&root, deps)?
+            .unwrap_or_else(|| object_id::<D>("tree", &[])))
+    }
+}
+
+impl Parse for GitTree {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        Ok(GitTree(input.parse()?))
+    }
+}
+
+/// Hashes a git object of type `kind`, whose header contains its type and length
+fn object_id<D: Digest>(kind: &str, contents: &[u8]) -> Vec<u8> {
+    let mut hasher = D::new();
+    hasher.update(format!("{} {}\0", kind, contents.len()));
+    hasher.update(contents);
+    hasher.finalize().to_vec()
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() & 0o100 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(_: &std::fs::Metadata) -> bool {
+    false
+}
@@ -0,0 +1,363 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::parse::{self, Parse, ParseStream};
+use syn::{Ident, LitByte, LitByteStr, LitChar, LitInt, LitStr, Token};
+
+use crate::builtin::Builtin;
+use crate::options::peek_option;
+
+pub trait ToBytes {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>>;
+}
+
+/// Files and environment variables read while evaluating the input of a macro
+#[derive(Default)]
+pub struct Dependencies {
+    files: Vec<PathBuf>,
+    env: Vec<String>,
+}
+
+impl Dependencies {
+    pub fn add_file(&mut self, path: PathBuf) {
+        if !self.files.contains(&path) {
+            self.files.push(path);
+        }
+    }
+
+    pub fn add_env(&mut self, name: String) {
+        if !self.env.contains(&name) {
+            self.env.push(name);
+        }
+    }
+
+    /// Wraps `output` in a block that passes every file to `include_bytes!` and every environment
+    /// variable to `option_env!`
+    ///
+    /// Cargo does not know about files and environment variables read by proc macros, so without
+    /// this, changing them would not cause the invoking crate to be recompiled. If there are no
+    /// dependencies, `output` is returned unchanged, so it can still be used as a pattern.
+    pub fn track(self, output: TokenStream) -> syn::Result<TokenStream> {
+        if self.files.is_empty() && self.env.is_empty() {
+            return Ok(output);
+        }
+
+        let paths = self
+            .files
+            .iter()
+            .map(|path| {
+                path.to_str().ok_or_else(|| {
+                    syn::Error::new(
+                        Span::call_site(),
+                        format!("path is not valid UTF-8: {}", path.display()),
+                    )
+                })
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let env = &self.env;
+        let output = proc_macro2::TokenStream::from(output);
+        Ok(quote! {
+            {
+                #(const _: &[u8] = ::core::include_bytes!(#paths);)*
+                #(const _: ::core::option::Option<&str> = ::core::option_env!(#env);)*
+                #output
+            }
+        }
+        .into())
+    }
+}
+
+/// One or more comma-separated literals, concatenated before hashing
+///
+/// Parsing stops at the first option, which is left to [`Args`](crate::options::Args).
+pub struct Input(Vec<Literal>);
+
+impl ToBytes for Input {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        for literal in &self.0 {
+            bytes.extend(literal.to_bytes(deps)?);
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        let mut literals = vec![input.parse()?];
+        while input.peek(Token![,]) && !peek_option(input) {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            literals.push(input.parse()?);
+        }
+
+        Ok(Input(literals))
+    }
+}
+
+/// The largest output length in bytes of hash functions with an extendable output
+#[cfg(any(feature = "sha3", feature = "blake2", feature = "blake3"))]
+pub const MAX_XOF_LEN: usize = 1024;
+
+/// Input of a hash function with a variable output length, which can be given in bytes as a
+/// trailing integer literal without a type suffix
+#[cfg(any(feature = "sha3", feature = "blake2", feature = "blake3"))]
+#[cfg_attr(not(feature = "sha3"), allow(dead_code))]
+pub struct XofInput {
+    input: Input,
+    len: Option<LitInt>,
+    span: Span,
+}
+
+#[cfg(any(feature = "sha3", feature = "blake2", feature = "blake3"))]
+#[cfg_attr(not(all(feature = "sha3", feature = "blake2")), allow(dead_code))]
+impl XofInput {
+    /// Returns the requested output length, or `default` if there is none
+    pub fn len_or(&self, default: usize) -> syn::Result<usize> {
+        match &self.len {
+            Some(len) => len.base10_parse(),
+            None => Ok(default),
+        }
+    }
+
+    /// Returns the requested output length, failing if there is none
+    pub fn required_len(&self) -> syn::Result<usize> {
+        match &self.len {
+            Some(len) => self.bounded_len(len.base10_parse()?, MAX_XOF_LEN),
+            None => Err(syn::Error::new(
+                self.span,
+                "expected the output length as the last argument, such as `32`",
+            )),
+        }
+    }
+
+    /// Fails if `len` is 0 or larger than `max`, such as [`MAX_XOF_LEN`] to keep the output of
+    /// extendable-output functions reasonably small
+    pub fn bounded_len(&self, len: usize, max: usize) -> syn::Result<usize> {
+        if (1..=max).contains(&len) {
+            Ok(len)
+        } else {
+            Err(self.len_error(format!("the output length must be between 1 and {}", max)))
+        }
+    }
+
+    pub fn len_error(&self, message: impl Display) -> syn::Error {
+        let span = self.len.as_ref().map_or(self.span, LitInt::span);
+        syn::Error::new(span, message)
+    }
+}
+
+#[cfg(any(feature = "sha3", feature = "blake2", feature = "blake3"))]
+impl ToBytes for XofInput {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        self.input.to_bytes(deps)
+    }
+}
+
+#[cfg(any(feature = "sha3", feature = "blake2", feature = "blake3"))]
+impl Parse for XofInput {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        let span = input.span();
+        let mut input: Input = input.parse()?;
+        let len = match input.0.last() {
+            Some(Literal::Int(None, len)) if len.suffix().is_empty() && input.0.len() > 1 => {
+                let len = len.clone();
+                input.0.pop();
+                Some(len)
+            }
+            _ => None,
+        };
+
+        Ok(XofInput { input, len, span })
+    }
+}
+
+/// Input of a keyed hash function, with the key given as the first argument
+#[cfg_attr(not(feature = "blake3"), allow(dead_code))]
+pub struct KeyedInput<I = Input> {
+    key: Literal,
+    key_span: Span,
+    pub input: I,
+}
+
+#[cfg_attr(not(feature = "blake3"), allow(dead_code))]
+impl<I> KeyedInput<I> {
+    pub fn key(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        self.key.to_bytes(deps)
+    }
+
+    pub fn key_error(&self, message: impl Display) -> syn::Error {
+        syn::Error::new(self.key_span, message)
+    }
+}
+
+impl<I: ToBytes> ToBytes for KeyedInput<I> {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        self.input.to_bytes(deps)
+    }
+}
+
+impl<I: Parse> Parse for KeyedInput<I> {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        let key_span = input.span();
+        let key = input.parse()?;
+        input.parse::<Token![,]>()?;
+        Ok(KeyedInput {
+            key,
+            key_span,
+            input: input.parse()?,
+        })
+    }
+}
+
+enum Literal {
+    String(LitStr),
+    Bytes(LitByteStr),
+    Char(LitChar),
+    Byte(LitByte),
+    Int(Option<Token![-]>, LitInt),
+    Macro(Builtin),
+}
+
+impl Literal {
+    fn int_to_bytes(minus: &Option<Token![-]>, x: &LitInt) -> syn::Result<Vec<u8>> {
+        let digits = match minus {
+            Some(_) => format!("-{}", x.base10_digits()),
+            None => x.base10_digits().to_owned(),
+        };
+
+        macro_rules! encode {
+            ($($ty:ident)*) => {
+                match x.suffix() {
+                    $(stringify!($ty) => digits
+                        .parse::<$ty>()
+                        .map(|x| x.to_be_bytes().to_vec())
+                        .map_err(|_| {
+                            syn::Error::new(x.span(), concat!("integer out of range for `", stringify!($ty), "`"))
+                        }),)*
+                    "" => Err(syn::Error::new(
+                        x.span(),
+                        "integer literals must have a type suffix, such as `1u32`",
+                    )),
+                    suffix => Err(syn::Error::new(
+                        x.span(),
+                        format!("unsupported integer type `{}`", suffix),
+                    )),
+                }
+            };
+        }
+
+        encode!(u8 u16 u32 u64 u128 i8 i16 i32 i64 i128)
+    }
+}
+
+impl ToBytes for Literal {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        Ok(match self {
+            Self::String(x) => x.value().into_bytes(),
+            Self::Bytes(x) => x.value(),
+            Self::Char(x) => x.value().to_string().into_bytes(),
+            Self::Byte(x) => vec![x.value()],
+            Self::Int(minus, x) => Self::int_to_bytes(minus, x)?,
+            Self::Macro(x) => x.to_bytes(deps)?,
+        })
+    }
+}
+
+impl Parse for Literal {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        if input.peek(LitStr) {
+            Ok(Literal::String(input.parse()?))
+        } else if input.peek(LitByteStr) {
+            Ok(Literal::Bytes(input.parse()?))
+        } else if input.peek(LitChar) {
+            Ok(Literal::Char(input.parse()?))
+        } else if input.peek(LitByte) {
+            Ok(Literal::Byte(input.parse()?))
+        } else if input.peek(LitInt) || (input.peek(Token![-]) && input.peek2(LitInt)) {
+            Ok(Literal::Int(input.parse()?, input.parse()?))
+        } else if input.peek(Token![::])
+            || (input.peek(Ident) && (input.peek2(Token![!]) || input.peek2(Token![::])))
+        {
+            Ok(Literal::Macro(input.parse()?))
+        } else {
+            Err(input.error("expected a literal or a macro call"))
+        }
+    }
+}
+
+/// Input of an assertion, followed by `==` and the expected hash as hexadecimal digits
+pub struct AssertInput<I> {
+    input: I,
+    pub expected: LitStr,
+}
+
+impl<I: ToBytes> ToBytes for AssertInput<I> {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        self.input.to_bytes(deps)
+    }
+}
+
+impl<I: Parse> Parse for AssertInput<I> {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        let args = input.parse()?;
+        input.parse::<Token![==]>()?;
+        Ok(AssertInput {
+            input: args,
+            expected: input.parse()?,
+        })
+    }
+}
+
+/// A path to a file, relative to the `CARGO_MANIFEST_DIR` of the crate invoking the macro
+pub struct FileInput {
+    path: LitStr,
+}
+
+impl FileInput {
+    pub fn resolve(&self) -> syn::Result<PathBuf> {
+        let path = PathBuf::from(self.path.value());
+        if path.is_absolute() {
+            return Ok(path);
+        }
+
+        let root = std::env::var_os("CARGO_MANIFEST_DIR")
+            .ok_or_else(|| syn::Error::new(self.path.span(), "CARGO_MANIFEST_DIR is not set"))?;
+        Ok(PathBuf::from(root).join(path))
+    }
+
+    pub fn error(&self, message: impl Display) -> syn::Error {
+        syn::Error::new(self.path.span(), message)
+    }
+}
+
+impl ToBytes for FileInput {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        let path = self.resolve()?;
+        let bytes = std::fs::read(&path)
+            .map_err(|e| self.error(format!("couldn't read {}: {}", path.display(), e)))?;
+
+        deps.add_file(path);
+        Ok(bytes)
+    }
+}
+
+impl Parse for FileInput {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        if input.peek(LitStr) {
+            Ok(FileInput {
+                path: input.parse()?,
+            })
+        } else {
+            Err(input.error("expected a string literal containing a path"))
+        }
+    }
+}
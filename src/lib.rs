@@ -6,70 +6,338 @@
 //! # use hex_literal::hex;
 //! assert_eq!(sha1_hex!("this is a test"), "fa26be19de6bff93f70bc2308434e4a440bbad02");
 //! assert_eq!(sha1_bytes!("this is a test"), hex!("fa26be19de6bff93f70bc2308434e4a440bbad02"));
+//! assert_eq!(sha1_file_hex!("tests/data/test.txt"), "fa26be19de6bff93f70bc2308434e4a440bbad02");
 //! ```
+//!
+//! Besides hexadecimal and base64, SHA1 hashes can be encoded as base32 (`sha1_base32!`), base58
+//! (`sha1_base58!`), base36 (`sha1_base36!`), Z85 (`sha1_z85!`) and Bech32 (`sha1_bech32!`).
+//! Their first bytes can be turned into integers with `sha1_u32!`, `sha1_u64!` and `sha1_u128!`,
+//! which can be used in patterns, and the whole hash into five 32-bit words with `sha1_words!`.
+//!
+//! Name-based UUIDs (version 5), which are derived from SHA1 hashes, are computed by `uuid_v5!`
+//! and `uuid_v5_bytes!`. With the `uuid` feature, `uuid_v5_uuid!` produces a `uuid::Uuid`.
+//!
+//! ```rust
+//! # use sha1_macros::*;
+//! assert_eq!(uuid_v5!(NAMESPACE_DNS, "example.com"), "cfbff0d1-9375-5685-968c-48ce8b15ae17");
+//! ```
+//!
+//! The IDs of git objects are computed by `git_blob_id!` for literals, `git_file_blob_id!` for
+//! files and `git_tree_id!` for directories, such as to check that vendored files match a commit.
+//! `websocket_accept!` computes the accept value of a WebSocket handshake for a given key. With the
+//! `sha2` feature, `sri!` and `sri_file!` compute Subresource Integrity values for web assets.
+//! `etag!` and `busted_name!` compute the `ETag` and a cache-busting name of a file.
+//!
+//! `assert_sha1!` and `assert_sha1_file!` fail compilation if the hash of their input is not the
+//! expected one, such as to guard vendored files against being edited.
+//!
+//! ```rust
+//! # use sha1_macros::*;
+//! assert_sha1_file!("tests/data/test.txt" == "fa26be19de6bff93f70bc2308434e4a440bbad02");
+//! ```
+//!
+//! # Hash functions
+//! HMAC-SHA1 is available through the `hmac_sha1_*` macros, which take the key as the first
+//! argument, followed by the message. Besides SHA1, macros for the following hash functions are
+//! available when enabling the corresponding cargo feature:
+//! - `sha2`: SHA-224, SHA-256, SHA-384, SHA-512 and SHA-512/256 (`sha256_hex!`, `sha512_bytes!`,
+//!   ...), as well as HMAC with all of them (`hmac_sha256_hex!`, `hmac_sha512_256_bytes!`, ...)
+//!   and Subresource Integrity values (`sri!`, `sri_file!`)
+//! - `sha3`: SHA3-224, SHA3-256, SHA3-384 and SHA3-512 (`sha3_256_hex!`, ...), as well as the
+//!   SHAKE128 and SHAKE256 extendable-output functions, whose output length is given in bytes as
+//!   the last argument (`shake256_bytes!(b"seed", 64)`), up to 1024 bytes
+//! - `blake2`: BLAKE2b and BLAKE2s, whose output length can be given in bytes as the last argument
+//! - `blake3`: BLAKE3, including its keyed hash (`blake3_keyed_hex!(key, message)`) and key
+//!   derivation (`blake3_derive_key_bytes!(context, material)`) modes, whose output length can be
+//!   given in bytes as the last argument, up to 1024 bytes
+//! - `md5`: MD5 (`md5_hex!`, ...)
+//!
+//! The following checksums and non-cryptographic hash functions produce integer literals instead,
+//! which can be used in patterns as long as their input does not read files or environment
+//! variables (see [Input](#input)):
+//! - `crc`: CRC-32 (`crc32!`), CRC-32C (`crc32c!`) and CRC-64 (`crc64!`)
+//! - `adler`: Adler-32 (`adler32!`)
+//! - `xxhash`: XXH32 (`xxh32!`), XXH64 (`xxh64!`) and XXH3 (`xxh3_64!`, `xxh3_128!`)
+//! - `fnv`: FNV-1 and FNV-1a (`fnv1_32!`, `fnv1a_32!`, `fnv1_64!`, `fnv1a_64!`)
+//!
+//! ```rust
+//! # #[cfg(feature = "crc")]
+//! # {
+//! # use sha1_macros::crc32;
+//! match 0x0d1ee7ea {
+//!     crc32!("this is a test") => {}
+//!     _ => unreachable!(),
+//! }
+//! # }
+//! ```
+//!
+//! # Input
+//! The `sha1_*` macros, except for the ones hashing files and directories, accept one or more
+//! comma-separated literals. Their encoded values are concatenated and then hashed as a whole:
+//! - string literals (`"value"`) and character literals (`'v'`) are encoded as UTF-8,
+//! - byte string literals (`b"value"`) and byte literals (`b'v'`) are used as they are,
+//! - integer literals (`42u32`, `-1i8`) are encoded as big-endian two's complement integers of
+//!   their type, which must be given as a suffix. `usize` and `isize` are not supported, as their
+//!   size depends on the target.
+//!
+//! ```rust
+//! # use sha1_macros::*;
+//! assert_eq!(sha1_hex!("this is", ' ', b"a", b' ', "test"), sha1_hex!("this is a test"));
+//! assert_eq!(sha1_hex!("key", 0x0102u16), sha1_hex!(b"key\x01\x02"));
+//! assert_eq!(sha1_hex!(-2i8, 255u8), sha1_hex!(b"\xfe\xff"));
+//! ```
+//!
+//! ```compile_fail
+//! # use sha1_macros::*;
+//! sha1_hex!("key", 1); // missing type suffix
+//! ```
+//!
+//! Calls of the following built-in macros are evaluated by the `sha1_*` macros themselves and can
+//! be used in place of a literal:
+//! - `concat!`, `stringify!`, `env!` and `include_str!`, which behave like their counterparts in
+//!   the standard library, except that `stringify!` may produce different whitespace,
+//! - `option_env!`, which contributes the value of the environment variable if it is defined and
+//!   nothing otherwise,
+//! - `include_bytes!`, which contributes the contents of the file.
+//!
+//! Like in the standard library, `include_str!` and `include_bytes!` resolve paths relative to the
+//! file containing the macro call, which is why this crate requires Rust 1.88 or newer. Changing
+//! an included file or a used environment variable causes the invoking crate to be recompiled.
+//!
+//! ```rust
+//! # use sha1_macros::*;
+//! assert_eq!(sha1_hex!(concat!("this is a ", "test")), sha1_hex!("this is a test"));
+//! assert_eq!(sha1_hex!(concat!("v", env!("CARGO_PKG_NAME"))), sha1_hex!("vsha1-macros"));
+//! assert_eq!(sha1_hex!(stringify!(this is a test)), sha1_hex!("this is a test"));
+//! assert_eq!(sha1_hex!(include_bytes!("../tests/data/test.txt")), sha1_hex!("this is a test"));
+//! ```
+//!
+//! ```compile_fail
+//! # use sha1_macros::*;
+//! const NAME: &str = "this is a test";
+//! sha1_hex!(NAME); // a constant is neither a literal nor a macro call
+//! ```
+//!
+//! To make Cargo aware of the files and environment variables read by a macro, its output is
+//! wrapped in a block whenever it reads any, including every macro hashing files or directories.
+//! A block is an expression, but unlike a literal it cannot be used in patterns, as an argument of
+//! `concat!` or as the value of an attribute such as `#[doc = ...]`.
+//!
+//! ```compile_fail
+//! # use sha1_macros::*;
+//! match 0 {
+//!     sha1_u32!(env!("CARGO_PKG_NAME")) => {} // a block is not a pattern
+//!     _ => {}
+//! }
+//! ```
+//!
+//! # Output options
+//! The output can be customized by options of the form `name = value`, which follow the input.
+//! Every `_hex` macro supports the following options:
+//! - `case = upper` produces uppercase digits instead of lowercase ones,
+//! - `sep = "..."` inserts a separator between every byte,
+//! - `group = N` inserts the separator between groups of `N` bytes instead.
+//!
+//! ```rust
+//! # use sha1_macros::*;
+//! assert_eq!(sha1_hex!("this is a test", case = upper), "FA26BE19DE6BFF93F70BC2308434E4A440BBAD02");
+//! assert_eq!(
+//!     sha1_hex!("this is a test", case = upper, sep = ":"),
+//!     "FA:26:BE:19:DE:6B:FF:93:F7:0B:C2:30:84:34:E4:A4:40:BB:AD:02",
+//! );
+//! assert_eq!(
+//!     sha1_file_hex!("tests/data/test.txt", sep = " ", group = 4),
+//!     "fa26be19 de6bff93 f70bc230 8434e4a4 40bbad02",
+//! );
+//! ```
+//!
+//! Every `_base64` macro supports the following options:
+//! - `alphabet = url_safe` uses `-` and `_` instead of `+` and `/`, `alphabet = bcrypt` and
+//!   `alphabet = crypt` use the alphabets of bcrypt and crypt(3), and `alphabet = standard` is the
+//!   default,
+//! - `pad` appends `=` padding characters.
+//!
+//! ```rust
+//! # use sha1_macros::*;
+//! assert_eq!(sha1_base64!("this is a test", pad), "+ia+Gd5r/5P3C8IwhDTkpEC7rQI=");
+//! assert_eq!(sha1_base64!("this is a test", alphabet = url_safe), "-ia-Gd5r_5P3C8IwhDTkpEC7rQI");
+//! assert_eq!(sha1_base64!("this is a test", alphabet = bcrypt), "8gY8Eb3p93N1A6GufBRinCA5pOG");
+//! ```
+//!
+//! Every `_hex`, `_base64` and `_bytes` macro, as well as `sha1_base32!`, `sha1_base58!` and
+//! `sha1_base36!`, supports `len = N`, which keeps only the first `N` characters or bytes, such as
+//! for abbreviated hashes. The resulting byte array is of type `[u8; N]`. `N` must not exceed the
+//! full length of the output. Outputs which would become invalid when truncated, such as Z85 and
+//! Bech32 strings or UUIDs, do not support it. For hexadecimal strings, `N` counts digits, not
+//! separators.
+//!
+//! ```rust
+//! # use sha1_macros::*;
+//! assert_eq!(sha1_hex!("this is a test", len = 7), "fa26be1");
+//! assert_eq!(sha1_hex!("this is a test", sep = ":", len = 8), "fa:26:be:19");
+//! assert_eq!(sha1_hex!("this is a test", sep = " ", group = 2, len = 7), "fa26 be1");
+//! assert_eq!(sha1_bytes!("this is a test", len = 4), [0xfa, 0x26, 0xbe, 0x19]);
+//! ```
+//!
+//! ```compile_fail
+//! # use sha1_macros::*;
+//! sha1_bytes!("this is a test", len = 21);
+//! ```
+//!
+//! ```compile_fail
+//! # use sha1_macros::*;
+//! sha1_bech32!("this is a test", hrp = "sha", len = 5);
+//! ```
+//!
+//! Every macro producing a string also supports `cstr`, which turns the result into a C string
+//! literal of type `&'static CStr`, such as for passing it to C functions.
+//!
+//! ```rust
+//! # use sha1_macros::*;
+//! use std::ffi::CStr;
+//!
+//! const HASH: &CStr = sha1_hex!("this is a test", cstr);
+//! assert_eq!(HASH, c"fa26be19de6bff93f70bc2308434e4a440bbad02");
+//! assert_eq!(sha1_base64!("this is a test", pad, cstr), c"+ia+Gd5r/5P3C8IwhDTkpEC7rQI=");
+//! ```
+//!
+//! Unsupported options are rejected.
+//! ```compile_fail
+//! # use sha1_macros::*;
+//! sha1_bytes!("this is a test", case = upper);
+//! ```
+
+mod builtin;
+mod dir;
+mod encode;
+mod git;
+mod input;
+mod options;
+mod uuid;
 
-use proc_macro::{Literal, Punct, Spacing, TokenStream, TokenTree};
+use proc_macro::TokenStream;
+#[cfg(any(
+    feature = "crc",
+    feature = "adler",
+    feature = "xxhash",
+    feature = "fnv"
+))]
+use proc_macro::{Literal, TokenTree};
+use quote::quote;
 use sha1::{Digest, Sha1};
-use syn::parse::{self, Parse, ParseStream};
-use syn::{parse_macro_input, LitByteStr, LitStr};
+use syn::parse::Parse;
+use syn::parse_macro_input;
 
-enum Input {
-    String(LitStr),
-    Bytes(LitByteStr),
-}
+use crate::dir::DirInput;
+use crate::encode::{
+    encode_base32, encode_base36, encode_base58, encode_base64, encode_bech32, encode_bytes,
+    encode_etag, encode_hex, encode_u128, encode_u32, encode_u64, encode_uuid, encode_words,
+    encode_z85, string_literal, truncated_len_or, Encoder,
+};
+use crate::git::{GitBlob, GitObject, GitTree};
+#[cfg(any(feature = "sha3", feature = "blake2", feature = "blake3"))]
+use crate::input::XofInput;
+#[cfg(feature = "blake3")]
+use crate::input::MAX_XOF_LEN;
+use crate::input::{AssertInput, Dependencies, FileInput, Input, KeyedInput, ToBytes};
+use crate::options::{Args, Options};
+use crate::uuid::UuidInput;
 
-impl Input {
-    pub fn to_bytes(&self) -> Vec<u8> {
-        match self {
-            Self::String(x) => x.value().into_bytes(),
-            Self::Bytes(x) => x.value(),
+/// Defines the `_hex`, `_base64` and `_bytes` macros of a hash function
+///
+/// The macros are implemented by calling `$impl` with the tokens passed to them and an encoder.
+/// Documentation comments are inserted after the summary line of every macro. The examples in the
+/// generated documentation assert that passing `$args` produces the given digests.
+macro_rules! hash_macros {
+    (
+        $(#[$attr:meta])*
+        $name:literal, $impl:expr, ($($args:tt)*),
+        $hex:ident = $hex_example:literal,
+        $base64:ident = $base64_example:literal,
+        $bytes:ident: [u8; $len:tt]
+        $(, invalid: [$($invalid:tt),* $(,)?])? $(,)?
+    ) => {
+        #[doc = concat!("Computes the ", $name, " hash as a hexadecimal string")]
+        #[doc = ""]
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = "The resulting value is of type `&'static str`. Its formatting can be changed with"]
+        #[doc = "[options](crate#output-options)."]
+        #[doc = "```rust"]
+        #[doc = concat!("# use sha1_macros::", stringify!($hex), ";")]
+        #[doc = concat!("assert_eq!(", stringify!($hex), "!", stringify!(($($args)*)), ", \"", $hex_example, "\");")]
+        #[doc = "```"]
+        $(
+            #[doc = ""]
+            #[doc = "Arguments such as the following fail to compile:"]
+            $(
+                #[doc = "```compile_fail"]
+                #[doc = concat!("# use sha1_macros::", stringify!($hex), ";")]
+                #[doc = concat!(stringify!($hex), "!", stringify!($invalid), ";")]
+                #[doc = "```"]
+            )*
+        )?
+        #[proc_macro]
+        pub fn $hex(tokens: TokenStream) -> TokenStream {
+            $impl(tokens, encode_hex)
+        }
+
+        #[doc = concat!("Computes the ", $name, " hash as a base64 unpadded string")]
+        #[doc = ""]
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = "The resulting value is of type `&'static str`. Its alphabet and padding can be changed"]
+        #[doc = "with [options](crate#output-options)."]
+        #[doc = "```rust"]
+        #[doc = concat!("# use sha1_macros::", stringify!($base64), ";")]
+        #[doc = concat!("assert_eq!(", stringify!($base64), "!", stringify!(($($args)*)), ", \"", $base64_example, "\");")]
+        #[doc = "```"]
+        #[proc_macro]
+        pub fn $base64(tokens: TokenStream) -> TokenStream {
+            $impl(tokens, encode_base64)
         }
-    }
-}
 
-impl Parse for Input {
-    fn parse(input: ParseStream) -> parse::Result<Self> {
-        if input.peek(LitStr) {
-            Ok(Input::String(input.parse()?))
-        } else if input.peek(LitByteStr) {
-            Ok(Input::Bytes(input.parse()?))
-        } else {
-            Err(input.error("expected a string or byte literal"))
+        #[doc = concat!("Computes the ", $name, " hash as a byte array")]
+        #[doc = ""]
+        $(#[$attr])*
+        #[doc = ""]
+        #[doc = concat!("The resulting value is of type `[u8; ", stringify!($len), "]`.")]
+        #[doc = "```rust"]
+        #[doc = concat!("# use sha1_macros::", stringify!($bytes), ";")]
+        #[doc = "# use hex_literal::hex;"]
+        #[doc = concat!("assert_eq!(", stringify!($bytes), "!", stringify!(($($args)*)), ", hex!(\"", $hex_example, "\"));")]
+        #[doc = "```"]
+        #[proc_macro]
+        pub fn $bytes(tokens: TokenStream) -> TokenStream {
+            $impl(tokens, encode_bytes)
         }
-    }
+    };
 }
 
 /// Computes the SHA1 hash as a hexadecimal string
 ///
-/// The resulting value is of type `&'static str`.
+/// The resulting value is of type `&'static str`. Its formatting can be changed with
+/// [options](crate#output-options).
 /// ```rust
 /// # use sha1_macros::sha1_hex;
 /// assert_eq!(sha1_hex!("this is a test"), "fa26be19de6bff93f70bc2308434e4a440bbad02");
 /// ```
 #[proc_macro]
 pub fn sha1_hex(tokens: TokenStream) -> TokenStream {
-    sha1_impl(tokens, |hash| {
-        let hash = hex::encode(hash);
-        TokenTree::Literal(Literal::string(hash.as_ref())).into()
-    })
+    sha1_impl::<Input>(tokens, encode_hex)
 }
 
 /// Computes the SHA1 hash as a base64 unpadded string
 ///
-/// The resulting value is of type `&'static str`.
+/// The resulting value is of type `&'static str`. Its alphabet and padding can be changed with
+/// [options](crate#output-options).
 /// ```rust
 /// # use sha1_macros::sha1_base64;
 /// assert_eq!(sha1_base64!("this is a test"), "+ia+Gd5r/5P3C8IwhDTkpEC7rQI");
 /// ```
 #[proc_macro]
 pub fn sha1_base64(tokens: TokenStream) -> TokenStream {
-    use base64::engine::general_purpose::STANDARD_NO_PAD;
-    use base64::Engine;
-
-    sha1_impl(tokens, |hash| {
-        let hash = STANDARD_NO_PAD.encode(hash);
-        TokenTree::Literal(Literal::string(hash.as_ref())).into()
-    })
+    sha1_impl::<Input>(tokens, encode_base64)
 }
 
 /// Computes the SHA1 hash as a byte array
@@ -82,21 +350,1264 @@ pub fn sha1_base64(tokens: TokenStream) -> TokenStream {
 /// ```
 #[proc_macro]
 pub fn sha1_bytes(tokens: TokenStream) -> TokenStream {
-    sha1_impl(tokens, |hash| {
-        TokenStream::from_iter([
-            TokenTree::Punct(Punct::new('*', Spacing::Joint)),
-            Literal::byte_string(hash).into(),
-        ])
+    sha1_impl::<Input>(tokens, encode_bytes)
+}
+
+/// Computes the SHA1 hash as a `Sha1Digest`
+///
+/// The resulting value is of type `sha1_const::Sha1Digest`, which requires depending on the
+/// [`sha1-const`](https://crates.io/crates/sha1-const) crate.
+/// ```rust
+/// # use sha1_macros::sha1_digest;
+/// use sha1_const::Sha1Digest;
+///
+/// const HASH: Sha1Digest = sha1_digest!("this is a test");
+/// assert_eq!(HASH, Sha1Digest::digest(b"this is a test"));
+/// assert_eq!(HASH.to_string(), "fa26be19de6bff93f70bc2308434e4a440bbad02");
+/// ```
+#[proc_macro]
+pub fn sha1_digest(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, |hash, _| {
+        let bytes = proc_macro2::Literal::byte_string(hash);
+        Ok(quote!(::sha1_const::Sha1Digest::from_bytes(*#bytes)).into())
     })
 }
 
-fn sha1_impl(tokens: TokenStream, f: impl FnOnce(&[u8]) -> TokenStream) -> TokenStream {
-    let input = parse_macro_input!(tokens as Input);
-    let bytes = input.to_bytes();
+/// Computes the SHA1 hash as a base32 string
+///
+/// Uses the uppercase alphabet of RFC 4648 without padding by default. `alphabet = crockford`
+/// selects Crockford's alphabet, `case = lower` produces lowercase letters and `pad` appends `=`
+/// padding characters. The resulting value is of type `&'static str`.
+/// ```rust
+/// # use sha1_macros::sha1_base32;
+/// assert_eq!(sha1_base32!("this is a test"), "7ITL4GO6NP7ZH5YLYIYIINHEURALXLIC");
+/// assert_eq!(sha1_base32!("this is a test", alphabet = crockford), "Z8KBW6EYDFZS7XRBR8R88D74MH0BQB82");
+/// ```
+#[proc_macro]
+pub fn sha1_base32(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, encode_base32)
+}
 
-    let mut hasher = Sha1::new();
-    hasher.update(bytes.as_slice());
+/// Computes the SHA1 hash as a base58 string
+///
+/// Uses the alphabet of Bitcoin, which leaves out `0`, `O`, `I` and `l`. Like in Bitcoin addresses,
+/// every leading zero byte of the hash is encoded as `1`. The resulting value is of type
+/// `&'static str`.
+/// ```rust
+/// # use sha1_macros::sha1_base58;
+/// assert_eq!(sha1_base58!("this is a test"), "4V8du6jeSjyLHQSD4CzfXCsXS4DP");
+/// ```
+#[proc_macro]
+pub fn sha1_base58(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, encode_base58)
+}
+
+/// Computes the SHA1 hash as a base36 string
+///
+/// The hash is encoded as a single number, padded with leading zeros to 31 digits so that every
+/// hash has the same length. `case = upper` produces uppercase letters. The resulting value is of
+/// type `&'static str`.
+/// ```rust
+/// # use sha1_macros::sha1_base36;
+/// assert_eq!(sha1_base36!("this is a test"), "t7xq3fsjic5xyny3jdp2i7hrrw8uhoi");
+/// ```
+#[proc_macro]
+pub fn sha1_base36(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, encode_base36)
+}
+
+/// Computes the SHA1 hash as a Z85 string
+///
+/// Z85 is the base85 encoding of ZeroMQ, whose output can be used in source code and XML without
+/// escaping. The resulting value is of type `&'static str`.
+/// ```rust
+/// # use sha1_macros::sha1_z85;
+/// assert_eq!(sha1_z85!("this is a test"), "}x&]]?FrdP{y1?(GF.+=k!Bc1");
+/// ```
+#[proc_macro]
+pub fn sha1_z85(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, encode_z85)
+}
 
-    let hash = hasher.finalize();
-    f(hash.as_ref())
+/// Computes the SHA1 hash as a Bech32 string
+///
+/// The human-readable part must be given as `hrp = "..."`. As Bech32 strings are limited to 90
+/// characters, it must be at most 51 characters long. `variant = bech32m` uses the checksum of
+/// Bech32m instead. The resulting value is of type `&'static str`.
+/// ```rust
+/// # use sha1_macros::sha1_bech32;
+/// assert_eq!(sha1_bech32!("this is a test", hrp = "sha"), "sha1lgntuxw7d0le8actcgcggd8y53qthtgzaddgxw");
+/// assert_eq!(
+///     sha1_bech32!("this is a test", hrp = "sha", variant = bech32m),
+///     "sha1lgntuxw7d0le8actcgcggd8y53qthtgzg3ayrv",
+/// );
+/// ```
+///
+/// ```compile_fail
+/// # use sha1_macros::sha1_bech32;
+/// sha1_bech32!("this is a test", hrp = "a-human-readable-part-that-is-far-too-long-for-bech32");
+/// ```
+#[proc_macro]
+pub fn sha1_bech32(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, encode_bech32)
+}
+
+/// Computes the SHA1 hash as a `u32` made of its first 4 bytes
+///
+/// The bytes are read as a big-endian integer, unless `endian = little` is given. Unlike strings
+/// and byte arrays, the resulting integer literal can be used in patterns, unless the input reads
+/// files or environment variables (see [Input](crate#input)).
+/// ```rust
+/// # use sha1_macros::sha1_u32;
+/// assert_eq!(sha1_u32!("this is a test"), 0xfa26be19);
+/// assert_eq!(sha1_u32!("this is a test", endian = little), 0x19be26fa);
+/// ```
+#[proc_macro]
+pub fn sha1_u32(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, encode_u32)
+}
+
+/// Computes the SHA1 hash as a `u64` made of its first 8 bytes
+///
+/// The bytes are read as a big-endian integer, unless `endian = little` is given. Unlike strings
+/// and byte arrays, the resulting integer literal can be used in patterns, unless the input reads
+/// files or environment variables (see [Input](crate#input)).
+/// ```rust
+/// # use sha1_macros::sha1_u64;
+/// const KEY: u64 = sha1_u64!("this is a test");
+/// assert_eq!(KEY, 0xfa26be19de6bff93);
+///
+/// match KEY {
+///     sha1_u64!("this is a test") => {}
+///     _ => unreachable!(),
+/// }
+/// ```
+#[proc_macro]
+pub fn sha1_u64(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, encode_u64)
+}
+
+/// Computes the SHA1 hash as a `u128` made of its first 16 bytes
+///
+/// The bytes are read as a big-endian integer, unless `endian = little` is given. Unlike strings
+/// and byte arrays, the resulting integer literal can be used in patterns, unless the input reads
+/// files or environment variables (see [Input](crate#input)).
+/// ```rust
+/// # use sha1_macros::sha1_u128;
+/// assert_eq!(sha1_u128!("this is a test"), 0xfa26be19de6bff93f70bc2308434e4a4);
+/// ```
+#[proc_macro]
+pub fn sha1_u128(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, encode_u128)
+}
+
+/// Computes the SHA1 hash as an array of five 32-bit words
+///
+/// The words are the final state `H0` to `H4` of SHA1, which are read from the hash as big-endian
+/// integers. `endian = little` reads them as little-endian integers instead. The resulting value
+/// is of type `[u32; 5]`.
+/// ```rust
+/// # use sha1_macros::sha1_words;
+/// assert_eq!(
+///     sha1_words!("this is a test"),
+///     [0xfa26be19, 0xde6bff93, 0xf70bc230, 0x8434e4a4, 0x40bbad02],
+/// );
+/// assert_eq!(
+///     sha1_words!("this is a test", endian = little),
+///     [0x19be26fa, 0x93ff6bde, 0x30c20bf7, 0xa4e43484, 0x02adbb40],
+/// );
+/// ```
+#[proc_macro]
+pub fn sha1_words(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<Input>(tokens, encode_words)
+}
+
+/// Computes a name-based UUID (version 5) as a hyphenated string
+///
+/// The first argument is the namespace, which is either `NAMESPACE_DNS`, `NAMESPACE_URL`,
+/// `NAMESPACE_OID`, `NAMESPACE_X500` or a string literal containing a UUID. It is followed by the
+/// name, which accepts the same literals as `sha1_hex!`. `case = upper` produces uppercase digits.
+/// A truncated UUID is not a UUID, so `len` is not supported. The resulting value is of type
+/// `&'static str`.
+/// ```rust
+/// # use sha1_macros::uuid_v5;
+/// assert_eq!(uuid_v5!(NAMESPACE_DNS, "example.com"), "cfbff0d1-9375-5685-968c-48ce8b15ae17");
+/// assert_eq!(
+///     uuid_v5!("6ba7b812-9dad-11d1-80b4-00c04fd430c8", "1.3.6.1"),
+///     "1447fa61-5277-5fef-a9b3-fbc6e44f4af3",
+/// );
+/// ```
+///
+/// ```compile_fail
+/// # use sha1_macros::uuid_v5;
+/// uuid_v5!(NAMESPACE_DNS, "example.com", len = 8);
+/// ```
+#[proc_macro]
+pub fn uuid_v5(tokens: TokenStream) -> TokenStream {
+    uuid_impl(tokens, encode_uuid)
+}
+
+/// Computes a name-based UUID (version 5) as a byte array
+///
+/// The arguments are the same as for `uuid_v5!`, and `len` is not supported either. The resulting
+/// value is of type `[u8; 16]`.
+/// ```rust
+/// # use sha1_macros::uuid_v5_bytes;
+/// # use hex_literal::hex;
+/// assert_eq!(uuid_v5_bytes!(NAMESPACE_DNS, "example.com"), hex!("cfbff0d193755685968c48ce8b15ae17"));
+/// ```
+#[proc_macro]
+pub fn uuid_v5_bytes(tokens: TokenStream) -> TokenStream {
+    uuid_impl(tokens, |uuid, _| {
+        let bytes = proc_macro2::Literal::byte_string(uuid);
+        Ok(quote!(*#bytes).into())
+    })
+}
+
+/// Computes a name-based UUID (version 5) as a `Uuid`
+///
+/// The arguments are the same as for `uuid_v5!`. The resulting value is of type `uuid::Uuid`,
+/// which requires depending on the [`uuid`](https://crates.io/crates/uuid) crate.
+/// ```rust
+/// # use sha1_macros::uuid_v5_uuid;
+/// use uuid::Uuid;
+///
+/// const ID: Uuid = uuid_v5_uuid!(NAMESPACE_URL, "https://example.com/");
+/// assert_eq!(ID, Uuid::parse_str("dd2c1780-811a-5296-81c5-178a0ef488bc").unwrap());
+/// ```
+#[cfg(feature = "uuid")]
+#[proc_macro]
+pub fn uuid_v5_uuid(tokens: TokenStream) -> TokenStream {
+    uuid_impl(tokens, |uuid, _| {
+        let bytes = proc_macro2::Literal::byte_string(uuid);
+        Ok(quote!(::uuid::Uuid::from_bytes(*#bytes)).into())
+    })
+}
+
+/// Computes the ID of a git blob containing the given literals as a hexadecimal string
+///
+/// The ID is the SHA1 hash of the contents preceded by a header, as computed by
+/// `git hash-object`. With the `sha2` feature, `object_format = sha256` computes the ID used by
+/// repositories with the SHA-256 object format instead. The resulting value is of type
+/// `&'static str`, which supports the same [options](crate#output-options) as `sha1_hex!`.
+/// ```rust
+/// # use sha1_macros::git_blob_id;
+/// assert_eq!(git_blob_id!("this is a test"), "a8a940627d132695a9769df883f85992f0ff4a43");
+/// assert_eq!(git_blob_id!("this is a test", len = 7), "a8a9406");
+/// # #[cfg(feature = "sha2")]
+/// assert_eq!(
+///     git_blob_id!("this is a test", object_format = sha256),
+///     "aa662eee4a787b375a5a373694d51988b9c3f2d28a92415bf4c4c7855f5ce2dc",
+/// );
+/// ```
+#[proc_macro]
+pub fn git_blob_id(tokens: TokenStream) -> TokenStream {
+    git_impl::<GitBlob<Input>>(tokens)
+}
+
+/// Computes the ID of a git blob containing a file as a hexadecimal string
+///
+/// The path is relative to the directory containing the `Cargo.toml` of the invoking crate. See
+/// `git_blob_id!` for the supported options.
+/// ```rust
+/// # use sha1_macros::git_file_blob_id;
+/// assert_eq!(git_file_blob_id!("tests/data/test.txt"), "a8a940627d132695a9769df883f85992f0ff4a43");
+/// ```
+#[proc_macro]
+pub fn git_file_blob_id(tokens: TokenStream) -> TokenStream {
+    git_impl::<GitBlob<FileInput>>(tokens)
+}
+
+/// Computes the ID of a git tree containing the files below a directory as a hexadecimal string
+///
+/// The path is relative to the directory containing the `Cargo.toml` of the invoking crate. Files
+/// are stored with mode 100644, or 100755 if they are executable by their owner, and symbolic
+/// links are stored as links. Like git, empty directories and `.git` directories are left out.
+/// Note that ignored files are not, so the ID only matches the one of a commit if the directory
+/// contains exactly the committed files. Other entries, such as named pipes, fail compilation.
+///
+/// Changing the contents of any of the files causes the invoking crate to be recompiled, but
+/// adding or removing files, changing the target of a link or changing whether a file is
+/// executable does not, so the ID may be outdated until the crate is rebuilt for another reason.
+/// See `git_blob_id!` for the supported options.
+/// ```rust
+/// # use sha1_macros::git_tree_id;
+/// assert_eq!(git_tree_id!("tests/data/tree"), "aa9e07fb25753c199531419d5609c69082ddb424");
+/// # #[cfg(feature = "sha2")]
+/// assert_eq!(
+///     git_tree_id!("tests/data/tree", object_format = sha256),
+///     "3badfdf6a4b3ac00a9a9977e585a5ad1b1977815374f02c78b8bbfef92956b56",
+/// );
+/// ```
+#[proc_macro]
+pub fn git_tree_id(tokens: TokenStream) -> TokenStream {
+    git_impl::<GitTree>(tokens)
+}
+
+/// Computes the `Sec-WebSocket-Accept` value for a `Sec-WebSocket-Key` of a WebSocket handshake
+///
+/// The value is the padded base64 encoding of the SHA1 hash of the key followed by the GUID
+/// defined in RFC 6455. The key must be the base64 encoding of 16 bytes. Clients reject truncated
+/// values, so `len` is not supported. The resulting value is of type `&'static str`.
+/// ```rust
+/// # use sha1_macros::websocket_accept;
+/// assert_eq!(websocket_accept!("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+/// ```
+///
+/// ```compile_fail
+/// # use sha1_macros::websocket_accept;
+/// websocket_accept!("dGhlIHNhbXBsZSBub25jZQ==", len = 3);
+/// ```
+///
+/// ```compile_fail
+/// # use sha1_macros::websocket_accept;
+/// websocket_accept!("abc"); // not the base64 encoding of 16 bytes
+/// ```
+#[proc_macro]
+pub fn websocket_accept(tokens: TokenStream) -> TokenStream {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    input_impl::<Input>(tokens, |_, key, _, options| {
+        if !matches!(STANDARD.decode(key), Ok(x) if x.len() == 16) {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "the key must be the base64 encoding of 16 bytes",
+            ));
+        }
+
+        let mut hasher = Sha1::new();
+        hasher.update(key);
+        hasher.update(WEBSOCKET_GUID);
+        string_literal(STANDARD.encode(hasher.finalize()), options)
+    })
+}
+
+/// Computes a Subresource Integrity value, such as for the `integrity` attribute of a `<script>`
+///
+/// The algorithms are selected by the options `sha256`, `sha384` and `sha512`, which default to
+/// `sha384`. If more than one is given, their values are separated by spaces. Browsers reject
+/// truncated values, so unlike `sha384_base64!`, `len` is not supported. The resulting value is of
+/// type `&'static str`.
+/// ```rust
+/// # use sha1_macros::sri;
+/// assert_eq!(sri!("this is a test"), "sha384-QzgqjMZQkEZ1ydYteFeG42jzqZ25muqqe3awJTBncVTQnAtr0uIbQyn9QVQ7mnhb");
+/// assert_eq!(
+///     sri!("this is a test", sha256, sha512),
+///     "sha256-Lpl1hUiXKo6IIq1H+hAX/3Lwbz/2oBaFH0XDmHMrxQw= \
+///      sha512-fQqEaO0iBADAuObzNbqn4HDOiAo34qxZlbmpe4CQJt5ibaY2rHNlJJu5dMcZ7fVDtS7ShmRvQ33H+BDMIGg3XA==",
+/// );
+/// ```
+///
+/// ```compile_fail
+/// # use sha1_macros::sri;
+/// sri!("this is a test", sha256, len = 3);
+/// ```
+#[cfg(feature = "sha2")]
+#[proc_macro]
+pub fn sri(tokens: TokenStream) -> TokenStream {
+    sri_impl::<Input>(tokens)
+}
+
+/// Computes a Subresource Integrity value of a file
+///
+/// The path is relative to the directory containing the `Cargo.toml` of the invoking crate. See
+/// `sri!` for the supported options.
+/// ```rust
+/// # use sha1_macros::sri_file;
+/// assert_eq!(sri_file!("tests/data/test.txt", sha256), "sha256-Lpl1hUiXKo6IIq1H+hAX/3Lwbz/2oBaFH0XDmHMrxQw=");
+/// ```
+#[cfg(feature = "sha2")]
+#[proc_macro]
+pub fn sri_file(tokens: TokenStream) -> TokenStream {
+    sri_impl::<FileInput>(tokens)
+}
+
+/// Computes a strong HTTP entity tag (`ETag`) of a file
+///
+/// The tag consists of the hexadecimal SHA1 hash of the file in double quotes, as required by the
+/// `ETag` header. `len = N` keeps only the first `N` digits. The path is relative to the directory
+/// containing the `Cargo.toml` of the invoking crate. The resulting value is of type
+/// `&'static str`.
+/// ```rust
+/// # use sha1_macros::etag;
+/// assert_eq!(etag!("tests/data/test.txt"), "\"fa26be19de6bff93f70bc2308434e4a440bbad02\"");
+/// assert_eq!(etag!("tests/data/test.txt", len = 16), "\"fa26be19de6bff93\"");
+/// ```
+#[proc_macro]
+pub fn etag(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<FileInput>(tokens, encode_etag)
+}
+
+/// Computes a cache-busting name of a file, which contains the start of its hash
+///
+/// The first 8 hexadecimal digits of the SHA1 hash of the file are inserted before its extension,
+/// so `app.css` becomes `app.fa26be19.css`. Only the name of the file is kept, not the directories
+/// leading to it. `len = N` changes the number of digits. The path is relative to the directory
+/// containing the `Cargo.toml` of the invoking crate. The resulting value is of type
+/// `&'static str`.
+/// ```rust
+/// # use sha1_macros::busted_name;
+/// assert_eq!(busted_name!("tests/data/test.txt"), "test.fa26be19.txt");
+/// assert_eq!(busted_name!("tests/data/tree/style.css", len = 12), "style.66d1c3395c76.css");
+/// ```
+#[proc_macro]
+pub fn busted_name(tokens: TokenStream) -> TokenStream {
+    input_impl::<FileInput>(tokens, |input, bytes, _, options| {
+        let path = input.resolve()?;
+        let name = path
+            .file_name()
+            .and_then(|x| x.to_str())
+            .ok_or_else(|| input.error("expected a path to a file with a UTF-8 name"))?;
+
+        let hash = hex::encode(Sha1::digest(bytes));
+        let len = truncated_len_or(8, hash.len(), "digits", options)?;
+        let hash = &hash[..len];
+
+        // a leading dot does not start an extension
+        let busted = match name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => {
+                format!("{}.{}.{}", stem, hash, extension)
+            }
+            _ => format!("{}.{}", name, hash),
+        };
+        string_literal(busted, options)
+    })
+}
+
+/// Asserts at compile-time that the SHA1 hash of the given literals is the expected one
+///
+/// The literals are followed by `==` and the expected hash as hexadecimal digits of either case.
+/// If the hashes differ, compilation fails with an error showing both of them. The macro can be
+/// used wherever an item or a statement is allowed.
+/// ```rust
+/// # use sha1_macros::assert_sha1;
+/// assert_sha1!("this is a test" == "fa26be19de6bff93f70bc2308434e4a440bbad02");
+/// ```
+///
+/// ```compile_fail
+/// # use sha1_macros::assert_sha1;
+/// assert_sha1!("this is a test" == "0000000000000000000000000000000000000000");
+/// ```
+#[proc_macro]
+pub fn assert_sha1(tokens: TokenStream) -> TokenStream {
+    assert_impl::<Input>(tokens)
+}
+
+/// Asserts at compile-time that the SHA1 hash of a file is the expected one
+///
+/// The path is relative to the directory containing the `Cargo.toml` of the invoking crate. It is
+/// followed by `==` and the expected hash, like for `assert_sha1!`. Changing the file causes the
+/// assertion to be checked again, so it can guard vendored files against being edited.
+/// ```rust
+/// # use sha1_macros::assert_sha1_file;
+/// assert_sha1_file!("tests/data/test.txt" == "fa26be19de6bff93f70bc2308434e4a440bbad02");
+/// ```
+#[proc_macro]
+pub fn assert_sha1_file(tokens: TokenStream) -> TokenStream {
+    assert_impl::<FileInput>(tokens)
+}
+
+/// Computes the SHA1 hash of a file as a hexadecimal string
+///
+/// The path is resolved relative to the directory containing the `Cargo.toml` of the crate
+/// invoking the macro. Changing the file causes the invoking crate to be recompiled. The resulting
+/// value is of type `&'static str`.
+/// ```rust
+/// # use sha1_macros::sha1_file_hex;
+/// assert_eq!(sha1_file_hex!("tests/data/test.txt"), "fa26be19de6bff93f70bc2308434e4a440bbad02");
+/// ```
+#[proc_macro]
+pub fn sha1_file_hex(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<FileInput>(tokens, encode_hex)
+}
+
+/// Computes the SHA1 hash of a file as a base64 unpadded string
+///
+/// The path is resolved relative to the directory containing the `Cargo.toml` of the crate
+/// invoking the macro. Changing the file causes the invoking crate to be recompiled. The resulting
+/// value is of type `&'static str`.
+/// ```rust
+/// # use sha1_macros::sha1_file_base64;
+/// assert_eq!(sha1_file_base64!("tests/data/test.txt"), "+ia+Gd5r/5P3C8IwhDTkpEC7rQI");
+/// ```
+#[proc_macro]
+pub fn sha1_file_base64(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<FileInput>(tokens, encode_base64)
+}
+
+/// Computes the SHA1 hash of a file as a byte array
+///
+/// The path is resolved relative to the directory containing the `Cargo.toml` of the crate
+/// invoking the macro. Changing the file causes the invoking crate to be recompiled. The resulting
+/// value is of type `[u8; 20]`.
+/// ```rust
+/// # use sha1_macros::sha1_file_bytes;
+/// # use hex_literal::hex;
+/// assert_eq!(sha1_file_bytes!("tests/data/test.txt"), hex!("fa26be19de6bff93f70bc2308434e4a440bbad02"));
+/// ```
+#[proc_macro]
+pub fn sha1_file_bytes(tokens: TokenStream) -> TokenStream {
+    sha1_impl::<FileInput>(tokens, encode_bytes)
+}
+
+/// Computes the SHA1 hash of a directory tree as a hexadecimal string
+///
+/// The path is resolved relative to the directory containing the `Cargo.toml` of the crate
+/// invoking the macro. Files can be filtered with `include` and `exclude` glob patterns, given
+/// either as a single string or as a list of strings, which are matched against `/`-separated
+/// paths relative to the directory, so `*` never matches a `/` but `**` does. A file is hashed if
+/// it matches any `include` pattern (or there are none) and no `exclude` pattern.
+///
+/// Every file is visited in order of its relative path, compared byte-wise, and contributes the
+/// following to the hashed data:
+/// - its relative path, encoded as UTF-8,
+/// - a NUL byte,
+/// - the length of its contents as a 64-bit big-endian integer,
+/// - its contents.
+///
+/// Changing any of the hashed files causes the invoking crate to be recompiled, but adding or
+/// removing files does not. The resulting value is of type `&'static str`.
+/// ```rust
+/// # use sha1_macros::sha1_dir_hex;
+/// assert_eq!(sha1_dir_hex!("tests/data/tree"), "f2d356b4fa2a4248322947c64a497de1a2ab9a84");
+/// assert_eq!(sha1_dir_hex!("tests/data/tree", include = "**/*.html"), "47edc4b5d76553c64c4f5c2cdc8773f8bd485e37");
+/// assert_eq!(
+///     sha1_dir_hex!("tests/data/tree", case = upper, include = "**/*.html"),
+///     "47EDC4B5D76553C64C4F5C2CDC8773F8BD485E37",
+/// );
+/// assert_eq!(
+///     sha1_dir_hex!("tests/data/tree", include = ["*.html", "*.css"], exclude = "drafts/**"),
+///     "0fac44e9f15d2025a174fe13f036532bdd237260",
+/// );
+/// ```
+#[proc_macro]
+pub fn sha1_dir_hex(tokens: TokenStream) -> TokenStream {
+    dir_impl(tokens, encode_hex)
+}
+
+/// Computes the SHA1 hash of a directory tree as a base64 unpadded string
+///
+/// Accepts the same arguments and hashes the same data as [`sha1_dir_hex!`]. The resulting value
+/// is of type `&'static str`.
+/// ```rust
+/// # use sha1_macros::sha1_dir_base64;
+/// assert_eq!(sha1_dir_base64!("tests/data/tree"), "8tNWtPoqQkgyKUfGSkl94aKrmoQ");
+/// ```
+#[proc_macro]
+pub fn sha1_dir_base64(tokens: TokenStream) -> TokenStream {
+    dir_impl(tokens, encode_base64)
+}
+
+/// Computes the SHA1 hash of a directory tree as a byte array
+///
+/// Accepts the same arguments and hashes the same data as [`sha1_dir_hex!`]. The resulting value
+/// is of type `[u8; 20]`.
+/// ```rust
+/// # use sha1_macros::sha1_dir_bytes;
+/// # use hex_literal::hex;
+/// assert_eq!(
+///     sha1_dir_bytes!("tests/data/tree", exclude = ["*.css", "drafts/*"]),
+///     hex!("3bde098dfb728231305b45a637df30caeac42aea"),
+/// );
+/// ```
+#[proc_macro]
+pub fn sha1_dir_bytes(tokens: TokenStream) -> TokenStream {
+    dir_impl(tokens, encode_bytes)
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    "SHA-224", digest_impl::<sha2::Sha224, Input>, ("this is a test"),
+    sha224_hex = "52fa5d621db1c9f11602fc92d1e8d1115a9018f191de948944c4ac39",
+    sha224_base64 = "UvpdYh2xyfEWAvyS0ejREVqQGPGR3pSJRMSsOQ",
+    sha224_bytes: [u8; 28],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    "SHA-256", digest_impl::<sha2::Sha256, Input>, ("this is a test"),
+    sha256_hex = "2e99758548972a8e8822ad47fa1017ff72f06f3ff6a016851f45c398732bc50c",
+    sha256_base64 = "Lpl1hUiXKo6IIq1H+hAX/3Lwbz/2oBaFH0XDmHMrxQw",
+    sha256_bytes: [u8; 32],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    "SHA-384", digest_impl::<sha2::Sha384, Input>, ("this is a test"),
+    sha384_hex = "43382a8cc650904675c9d62d785786e368f3a99db99aeaaa7b76b02530677154d09c0b6bd2e21b4329fd41543b9a785b",
+    sha384_base64 = "QzgqjMZQkEZ1ydYteFeG42jzqZ25muqqe3awJTBncVTQnAtr0uIbQyn9QVQ7mnhb",
+    sha384_bytes: [u8; 48],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    "SHA-512", digest_impl::<sha2::Sha512, Input>, ("this is a test"),
+    sha512_hex = "7d0a8468ed220400c0b8e6f335baa7e070ce880a37e2ac5995b9a97b809026de626da636ac7365249bb974c719edf543b52ed286646f437dc7f810cc2068375c",
+    sha512_base64 = "fQqEaO0iBADAuObzNbqn4HDOiAo34qxZlbmpe4CQJt5ibaY2rHNlJJu5dMcZ7fVDtS7ShmRvQ33H+BDMIGg3XA",
+    sha512_bytes: [u8; 64],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    "SHA-512/256", digest_impl::<sha2::Sha512_256, Input>, ("this is a test"),
+    sha512_256_hex = "6c53016ac6f75b6a86dbd56070cbed58a5880071fa3ae44f1211ec72958ae941",
+    sha512_256_base64 = "bFMBasb3W2qG29VgcMvtWKWIAHH6OuRPEhHscpWK6UE",
+    sha512_256_bytes: [u8; 32],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha3")]
+    "SHA3-224", digest_impl::<sha3::Sha3_224, Input>, ("this is a test"),
+    sha3_224_hex = "11bba509f26b29918b79e77f4bcd1ff9cbc1bcf09f30a007cfe20223",
+    sha3_224_base64 = "EbulCfJrKZGLeed/S80f+cvBvPCfMKAHz+ICIw",
+    sha3_224_bytes: [u8; 28],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha3")]
+    "SHA3-256", digest_impl::<sha3::Sha3_256, Input>, ("this is a test"),
+    sha3_256_hex = "b69ced483a79e37e13372af7cdd9e6757542fe91be6e6d05b654fa19dab9056d",
+    sha3_256_base64 = "tpztSDp5434TNyr3zdnmdXVC/pG+bm0FtlT6Gdq5BW0",
+    sha3_256_bytes: [u8; 32],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha3")]
+    "SHA3-384", digest_impl::<sha3::Sha3_384, Input>, ("this is a test"),
+    sha3_384_hex = "717c45d6c27403250d24e632bea215668ba2f217fbbafe927160245b78d16ca0bd3cd7c3e9e322af99b5b5689635f40b",
+    sha3_384_base64 = "cXxF1sJ0AyUNJOYyvqIVZoui8hf7uv6ScWAkW3jRbKC9PNfD6eMir5m1tWiWNfQL",
+    sha3_384_bytes: [u8; 48],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha3")]
+    "SHA3-512", digest_impl::<sha3::Sha3_512, Input>, ("this is a test"),
+    sha3_512_hex = "a68bf14c14f2f81a3ed31a849cc104555834af7dd7cc586829debd330641d9f799d5f261201a54802157f40aa3435b495f2f060831c28edd79cf0bf43938a3ac",
+    sha3_512_base64 = "povxTBTy+Bo+0xqEnMEEVVg0r33XzFhoKd69MwZB2feZ1fJhIBpUgCFX9AqjQ1tJXy8GCDHCjt15zwv0OTijrA",
+    sha3_512_bytes: [u8; 64],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha3")]
+    /// The length of the hash, `N`, is given in bytes as the last argument. It must be between 1
+    /// and 1024.
+    "SHAKE128", xof_impl::<sha3::Shake128>, ("this is a test", 16),
+    shake128_hex = "1e5c3709a40f1f26e7a61602842faa33",
+    shake128_base64 = "Hlw3CaQPHybnphYChC+qMw",
+    shake128_bytes: [u8; N],
+    invalid: [("this is a test", 0), ("this is a test", 1025)],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha3")]
+    /// The length of the hash, `N`, is given in bytes as the last argument. It must be between 1
+    /// and 1024.
+    "SHAKE256", xof_impl::<sha3::Shake256>, ("this is a test", 32),
+    shake256_hex = "a8cb8c72bc335a68a40837fc401fa7bfeddba83254e6f2898b687132c6239777",
+    shake256_base64 = "qMuMcrwzWmikCDf8QB+nv+3bqDJU5vKJi2hxMsYjl3c",
+    shake256_bytes: [u8; N],
+}
+
+hash_macros! {
+    #[cfg(feature = "blake2")]
+    /// The length of the hash, `N`, can be given in bytes as the last argument. It must be between 1
+    /// and 64 and defaults to 64.
+    "BLAKE2b", blake2_impl::<blake2::Blake2bVar>, ("this is a test"),
+    blake2b_hex = "61a548f2de1c318ba91d5207007861010f69a43ec663fe487d8403282c934ea725dc0bb172256ac99625ad64cca6a2c4d61c650a35afab4787dc678e19071ef9",
+    blake2b_base64 = "YaVI8t4cMYupHVIHAHhhAQ9ppD7GY/5IfYQDKCyTTqcl3AuxciVqyZYlrWTMpqLE1hxlCjWvq0eH3GeOGQce+Q",
+    blake2b_bytes: [u8; N],
+    invalid: [("this is a test", 0), ("this is a test", 65)],
+}
+
+hash_macros! {
+    #[cfg(feature = "blake2")]
+    /// The length of the hash, `N`, can be given in bytes as the last argument. It must be between 1
+    /// and 32 and defaults to 32.
+    "BLAKE2s", blake2_impl::<blake2::Blake2sVar>, ("this is a test", 16),
+    blake2s_hex = "bd1ab90f01b37f80d81c95687d0e7efd",
+    blake2s_base64 = "vRq5DwGzf4DYHJVofQ5+/Q",
+    blake2s_bytes: [u8; N],
+    invalid: [("this is a test", 0)],
+}
+
+hash_macros! {
+    #[cfg(feature = "blake3")]
+    /// The length of the hash, `N`, can be given in bytes as the last argument. It must be between 1
+    /// and 1024 and defaults to 32.
+    "BLAKE3", blake3_impl, ("this is a test"),
+    blake3_hex = "517f9ef9cadb0c30f1df5555a4e97bffcc0a279e86cd3fb2cdcb952110873a31",
+    blake3_base64 = "UX+e+crbDDDx31VVpOl7/8wKJ56GzT+yzcuVIRCHOjE",
+    blake3_bytes: [u8; N],
+    invalid: [("this is a test", 0), ("this is a test", 1025)],
+}
+
+hash_macros! {
+    #[cfg(feature = "blake3")]
+    /// The key is given as the first argument and must be 32 bytes long. The length of the hash,
+    /// `N`, can be given in bytes as the last argument. It must be between 1 and 1024 and defaults
+    /// to 32.
+    "BLAKE3 keyed", blake3_keyed_impl, (b"whats the Elvish word for friend", "this is a test"),
+    blake3_keyed_hex = "e14d4492e26b18cfebd0a69d3caceb6eec8a129699f5f20b84b290290f3019d6",
+    blake3_keyed_base64 = "4U1EkuJrGM/r0KadPKzrbuyKEpaZ9fILhLKQKQ8wGdY",
+    blake3_keyed_bytes: [u8; N],
+}
+
+hash_macros! {
+    #[cfg(feature = "blake3")]
+    /// Derives a key from the key material in the remaining arguments, using the context string
+    /// given as the first argument. The context should be hardcoded, globally unique and
+    /// application-specific. The length of the key, `N`, can be given in bytes as the last argument.
+    /// It must be between 1 and 1024 and defaults to 32.
+    "BLAKE3 key derivation", blake3_derive_key_impl, ("sha1-macros 2026-10-16 example context", "this is a test", 16),
+    blake3_derive_key_hex = "334192ef2930a132882f6528c9412133",
+    blake3_derive_key_base64 = "M0GS7ykwoTKIL2UoyUEhMw",
+    blake3_derive_key_bytes: [u8; N],
+}
+
+hash_macros! {
+    #[cfg(feature = "md5")]
+    "MD5", digest_impl::<md5::Md5, Input>, ("this is a test"),
+    md5_hex = "54b0c58c7ce9f2a8b551351102ee0938",
+    md5_base64 = "VLDFjHzp8qi1UTURAu4JOA",
+    md5_bytes: [u8; 16],
+}
+
+hash_macros! {
+    /// The key is given as the first argument, followed by the message.
+    "HMAC-SHA1", hmac_impl::<Sha1>, (b"key", "The quick brown fox jumps over the lazy dog"),
+    hmac_sha1_hex = "de7c9b85b8b78aa6bc8a7a36f70a90701c9db4d9",
+    hmac_sha1_base64 = "3nybhbi3iqa8ino29wqQcBydtNk",
+    hmac_sha1_bytes: [u8; 20],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    /// The key is given as the first argument, followed by the message.
+    "HMAC-SHA-224", hmac_impl::<sha2::Sha224>, (b"key", "The quick brown fox jumps over the lazy dog"),
+    hmac_sha224_hex = "88ff8b54675d39b8f72322e65ff945c52d96379988ada25639747e69",
+    hmac_sha224_base64 = "iP+LVGddObj3IyLmX/lFxS2WN5mIraJWOXR+aQ",
+    hmac_sha224_bytes: [u8; 28],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    /// The key is given as the first argument, followed by the message.
+    "HMAC-SHA-256", hmac_impl::<sha2::Sha256>, (b"key", "The quick brown fox jumps over the lazy dog"),
+    hmac_sha256_hex = "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+    hmac_sha256_base64 = "97yD9DBThCSxMpjmqm+xQ+9NWaFJRhdZl0edvC0aPNg",
+    hmac_sha256_bytes: [u8; 32],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    /// The key is given as the first argument, followed by the message.
+    "HMAC-SHA-384", hmac_impl::<sha2::Sha384>, (b"key", "The quick brown fox jumps over the lazy dog"),
+    hmac_sha384_hex = "d7f4727e2c0b39ae0f1e40cc96f60242d5b7801841cea6fc592c5d3e1ae50700582a96cf35e1e554995fe4e03381c237",
+    hmac_sha384_base64 = "1/RyfiwLOa4PHkDMlvYCQtW3gBhBzqb8WSxdPhrlBwBYKpbPNeHlVJlf5OAzgcI3",
+    hmac_sha384_bytes: [u8; 48],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    /// The key is given as the first argument, followed by the message.
+    "HMAC-SHA-512", hmac_impl::<sha2::Sha512>, (b"key", "The quick brown fox jumps over the lazy dog"),
+    hmac_sha512_hex = "b42af09057bac1e2d41708e48a902e09b5ff7f12ab428a4fe86653c73dd248fb82f948a549f7b791a5b41915ee4d1ec3935357e4e2317250d0372afa2ebeeb3a",
+    hmac_sha512_base64 = "tCrwkFe6weLUFwjkipAuCbX/fxKrQopP6GZTxz3SSPuC+UilSfe3kaW0GRXuTR7Dk1NX5OIxclDQNyr6Lr7rOg",
+    hmac_sha512_bytes: [u8; 64],
+}
+
+hash_macros! {
+    #[cfg(feature = "sha2")]
+    /// The key is given as the first argument, followed by the message.
+    "HMAC-SHA-512/256", hmac_impl::<sha2::Sha512_256>, (b"key", "The quick brown fox jumps over the lazy dog"),
+    hmac_sha512_256_hex = "7fb65e03577da9151a1016e9c2e514d4d48842857f13927f348588173dca6d89",
+    hmac_sha512_256_base64 = "f7ZeA1d9qRUaEBbpwuUU1NSIQoV/E5J/NIWIFz3KbYk",
+    hmac_sha512_256_bytes: [u8; 32],
+}
+
+/// Computes the CRC-32 checksum
+///
+/// Uses the CRC-32/ISO-HDLC algorithm, as used by zlib, gzip and PNG. The resulting value is of
+/// type `u32`.
+/// ```rust
+/// # use sha1_macros::crc32;
+/// assert_eq!(crc32!("this is a test"), 0x0d1ee7ea);
+/// ```
+#[cfg(feature = "crc")]
+#[proc_macro]
+pub fn crc32(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        let crc = crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC);
+        Literal::u32_suffixed(crc.checksum(bytes))
+    })
+}
+
+/// Computes the CRC-32C checksum
+///
+/// Uses the CRC-32/ISCSI algorithm with the Castagnoli polynomial, as used by iSCSI, SCTP and ext4.
+/// The resulting value is of type `u32`.
+/// ```rust
+/// # use sha1_macros::crc32c;
+/// assert_eq!(crc32c!("this is a test"), 0x7cfc66a7);
+/// ```
+#[cfg(feature = "crc")]
+#[proc_macro]
+pub fn crc32c(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        let crc = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI);
+        Literal::u32_suffixed(crc.checksum(bytes))
+    })
+}
+
+/// Computes the CRC-64 checksum
+///
+/// Uses the CRC-64/XZ algorithm, as used by xz. The resulting value is of type `u64`.
+/// ```rust
+/// # use sha1_macros::crc64;
+/// assert_eq!(crc64!("this is a test"), 0x40440ceabeca620e);
+/// ```
+#[cfg(feature = "crc")]
+#[proc_macro]
+pub fn crc64(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        let crc = crc::Crc::<u64>::new(&crc::CRC_64_XZ);
+        Literal::u64_suffixed(crc.checksum(bytes))
+    })
+}
+
+/// Computes the Adler-32 checksum
+///
+/// The resulting value is of type `u32`.
+/// ```rust
+/// # use sha1_macros::adler32;
+/// assert_eq!(adler32!("this is a test"), 0x26330516);
+/// ```
+#[cfg(feature = "adler")]
+#[proc_macro]
+pub fn adler32(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        Literal::u32_suffixed(adler::adler32_slice(bytes))
+    })
+}
+
+/// Computes the 32-bit xxHash (XXH32) with a seed of 0
+///
+/// The resulting value is of type `u32`.
+/// ```rust
+/// # use sha1_macros::xxh32;
+/// assert_eq!(xxh32!("this is a test"), 0x9b90997c);
+/// ```
+#[cfg(feature = "xxhash")]
+#[proc_macro]
+pub fn xxh32(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        Literal::u32_suffixed(xxhash_rust::xxh32::xxh32(bytes, 0))
+    })
+}
+
+/// Computes the 64-bit xxHash (XXH64) with a seed of 0
+///
+/// The resulting value is of type `u64`.
+/// ```rust
+/// # use sha1_macros::xxh64;
+/// assert_eq!(xxh64!("this is a test"), 0x5e83d5820d0f7f82);
+/// ```
+#[cfg(feature = "xxhash")]
+#[proc_macro]
+pub fn xxh64(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        Literal::u64_suffixed(xxhash_rust::xxh64::xxh64(bytes, 0))
+    })
+}
+
+/// Computes the 64-bit XXH3 hash with a seed of 0
+///
+/// The resulting value is of type `u64`.
+/// ```rust
+/// # use sha1_macros::xxh3_64;
+/// assert_eq!(xxh3_64!("this is a test"), 0xdd8e857ecbe79da0);
+/// ```
+#[cfg(feature = "xxhash")]
+#[proc_macro]
+pub fn xxh3_64(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        Literal::u64_suffixed(xxhash_rust::xxh3::xxh3_64(bytes))
+    })
+}
+
+/// Computes the 128-bit XXH3 hash with a seed of 0
+///
+/// The resulting value is of type `u128`.
+/// ```rust
+/// # use sha1_macros::xxh3_128;
+/// assert_eq!(xxh3_128!("this is a test"), 0xc869f15d341a0283859ff151d22f548c);
+/// ```
+#[cfg(feature = "xxhash")]
+#[proc_macro]
+pub fn xxh3_128(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        Literal::u128_suffixed(xxhash_rust::xxh3::xxh3_128(bytes))
+    })
+}
+
+/// Computes the 32-bit FNV-1 hash
+///
+/// The resulting value is of type `u32`.
+/// ```rust
+/// # use sha1_macros::fnv1_32;
+/// assert_eq!(fnv1_32!("this is a test"), 0x3ccfdf3a);
+/// ```
+#[cfg(feature = "fnv")]
+#[proc_macro]
+pub fn fnv1_32(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        let hash = bytes.iter().fold(FNV32_OFFSET, |hash, &x| {
+            hash.wrapping_mul(FNV32_PRIME) ^ u32::from(x)
+        });
+        Literal::u32_suffixed(hash)
+    })
+}
+
+/// Computes the 32-bit FNV-1a hash
+///
+/// The resulting value is of type `u32`.
+/// ```rust
+/// # use sha1_macros::fnv1a_32;
+/// assert_eq!(fnv1a_32!("this is a test"), 0x7f8dc2c0);
+/// ```
+#[cfg(feature = "fnv")]
+#[proc_macro]
+pub fn fnv1a_32(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        let hash = bytes.iter().fold(FNV32_OFFSET, |hash, &x| {
+            (hash ^ u32::from(x)).wrapping_mul(FNV32_PRIME)
+        });
+        Literal::u32_suffixed(hash)
+    })
+}
+
+/// Computes the 64-bit FNV-1 hash
+///
+/// The resulting value is of type `u64`.
+/// ```rust
+/// # use sha1_macros::fnv1_64;
+/// assert_eq!(fnv1_64!("this is a test"), 0x3a964561028aa4da);
+/// ```
+#[cfg(feature = "fnv")]
+#[proc_macro]
+pub fn fnv1_64(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        let hash = bytes.iter().fold(FNV64_OFFSET, |hash, &x| {
+            hash.wrapping_mul(FNV64_PRIME) ^ u64::from(x)
+        });
+        Literal::u64_suffixed(hash)
+    })
+}
+
+/// Computes the 64-bit FNV-1a hash
+///
+/// The resulting value is of type `u64`.
+/// ```rust
+/// # use sha1_macros::fnv1a_64;
+/// assert_eq!(fnv1a_64!("this is a test"), 0x38111b14ff242100);
+/// ```
+#[cfg(feature = "fnv")]
+#[proc_macro]
+pub fn fnv1a_64(tokens: TokenStream) -> TokenStream {
+    checksum_impl(tokens, |bytes| {
+        let hash = bytes.iter().fold(FNV64_OFFSET, |hash, &x| {
+            (hash ^ u64::from(x)).wrapping_mul(FNV64_PRIME)
+        });
+        Literal::u64_suffixed(hash)
+    })
+}
+
+/// Appended to the key of a WebSocket handshake before hashing it, as defined in RFC 6455
+const WEBSOCKET_GUID: &[u8] = b"258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+#[cfg(feature = "fnv")]
+const FNV32_OFFSET: u32 = 0x811c9dc5;
+#[cfg(feature = "fnv")]
+const FNV32_PRIME: u32 = 0x01000193;
+#[cfg(feature = "fnv")]
+const FNV64_OFFSET: u64 = 0xcbf29ce484222325;
+#[cfg(feature = "fnv")]
+const FNV64_PRIME: u64 = 0x00000100000001b3;
+
+fn sha1_impl<I: Parse + ToBytes>(tokens: TokenStream, f: impl Encoder) -> TokenStream {
+    digest_impl::<Sha1, I>(tokens, f)
+}
+
+#[cfg(feature = "sha2")]
+fn sri_impl<I: Parse + ToBytes>(tokens: TokenStream) -> TokenStream {
+    input_impl::<I>(tokens, |_, bytes, _, options| {
+        let mut values = Vec::new();
+        if options.flag("sha256")? {
+            values.push(sri_value::<sha2::Sha256>("sha256", bytes));
+        }
+
+        if options.flag("sha384")? {
+            values.push(sri_value::<sha2::Sha384>("sha384", bytes));
+        }
+
+        if options.flag("sha512")? {
+            values.push(sri_value::<sha2::Sha512>("sha512", bytes));
+        }
+
+        if values.is_empty() {
+            values.push(sri_value::<sha2::Sha384>("sha384", bytes));
+        }
+
+        string_literal(values.join(" "), options)
+    })
+}
+
+#[cfg(feature = "sha2")]
+fn sri_value<D: Digest>(name: &str, bytes: &[u8]) -> String {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+
+    format!("{}-{}", name, STANDARD.encode(D::digest(bytes)))
+}
+
+/// Compares the SHA1 hash of the input to the expected one, failing with both of them if they
+/// differ
+///
+/// The output is a `const` item, so that the tracked dependencies can be placed wherever an item
+/// or a statement is allowed.
+fn assert_impl<I: Parse + ToBytes>(tokens: TokenStream) -> TokenStream {
+    let output = input_impl::<AssertInput<I>>(tokens, |input, bytes, _, _| {
+        let expected = input.expected.value();
+        if expected.len() != 40 || !expected.bytes().all(|x| x.is_ascii_hexdigit()) {
+            return Err(syn::Error::new(
+                input.expected.span(),
+                "expected a SHA1 hash of 40 hexadecimal digits",
+            ));
+        }
+
+        let actual = hex::encode(Sha1::digest(bytes));
+        if expected.eq_ignore_ascii_case(&actual) {
+            return Ok(quote!(()).into());
+        }
+
+        let marker: String = expected
+            .chars()
+            .zip(actual.chars())
+            .map(|(a, b)| if a.eq_ignore_ascii_case(&b) { ' ' } else { '^' })
+            .collect();
+        Err(syn::Error::new(
+            input.expected.span(),
+            format!(
+                "SHA1 hash mismatch\n  expected: {}\n    actual: {}\n            {}",
+                expected,
+                actual,
+                marker.trim_end()
+            ),
+        ))
+    });
+    let output = proc_macro2::TokenStream::from(output);
+    quote!(const _: () = #output;).into()
+}
+
+/// Computes the ID of a git object as a hexadecimal string, using SHA-256 instead of SHA1 if
+/// `object_format = sha256` is given
+fn git_impl<I: Parse + GitObject>(tokens: TokenStream) -> TokenStream {
+    args_impl::<I>(tokens, |input, deps, options| {
+        let id = match options.keyword("object_format", &["sha1", "sha256"])? {
+            #[cfg(feature = "sha2")]
+            Some("sha256") => input.id::<sha2::Sha256>(deps)?,
+            #[cfg(not(feature = "sha2"))]
+            Some("sha256") => {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "`object_format = sha256` requires the `sha2` feature",
+                ))
+            }
+            _ => input.id::<Sha1>(deps)?,
+        };
+
+        encode_hex(&id, options)
+    })
+}
+
+/// Hashes the files below a directory, filtered by the `include` and `exclude` options
+fn dir_impl(tokens: TokenStream, f: impl Encoder) -> TokenStream {
+    args_impl::<FileInput>(tokens, |dir, deps, options| {
+        let bytes = DirInput::new(dir, options)?.to_bytes(deps)?;
+        f(&Sha1::digest(bytes), options)
+    })
+}
+
+/// Computes a UUID of version 5 from the first 16 bytes of the SHA1 hash
+fn uuid_impl(tokens: TokenStream, f: impl Encoder) -> TokenStream {
+    sha1_impl::<UuidInput>(tokens, |hash, options| {
+        let mut uuid = hash[..16].to_vec();
+        uuid[6] = (uuid[6] & 0x0f) | 0x50;
+        uuid[8] = (uuid[8] & 0x3f) | 0x80;
+        f(&uuid, options)
+    })
+}
+
+fn digest_impl<D: Digest, I: Parse + ToBytes>(tokens: TokenStream, f: impl Encoder) -> TokenStream {
+    input_impl::<I>(tokens, |_, bytes, _, options| {
+        let mut hasher = D::new();
+        hasher.update(bytes);
+
+        let hash = hasher.finalize();
+        f(hash.as_ref(), options)
+    })
+}
+
+fn hmac_impl<D: Digest + sha1::digest::core_api::BlockSizeUser>(
+    tokens: TokenStream,
+    f: impl Encoder,
+) -> TokenStream {
+    use hmac::{Mac, SimpleHmac};
+
+    input_impl::<KeyedInput>(tokens, |input, bytes, deps, options| {
+        let key = input.key(deps)?;
+        let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(&key)
+            .expect("HMAC should accept keys of any length");
+        mac.update(bytes);
+
+        let hash = mac.finalize().into_bytes();
+        f(hash.as_ref(), options)
+    })
+}
+
+#[cfg(feature = "sha3")]
+fn xof_impl<D: Default + sha1::digest::Update + sha1::digest::ExtendableOutput>(
+    tokens: TokenStream,
+    f: impl Encoder,
+) -> TokenStream {
+    input_impl::<XofInput>(tokens, |input, bytes, _, options| {
+        let mut hasher = D::default();
+        hasher.update(bytes);
+
+        let mut hash = vec![0; input.required_len()?];
+        hasher.finalize_xof_into(&mut hash);
+        f(&hash, options)
+    })
+}
+
+#[cfg(feature = "blake2")]
+fn blake2_impl<D: sha1::digest::Update + sha1::digest::VariableOutput>(
+    tokens: TokenStream,
+    f: impl Encoder,
+) -> TokenStream {
+    input_impl::<XofInput>(tokens, |input, bytes, _, options| {
+        // `new` accepts a length of 0, which would produce an empty hash
+        let len = input.bounded_len(input.len_or(D::MAX_OUTPUT_SIZE)?, D::MAX_OUTPUT_SIZE)?;
+        let mut hasher = D::new(len).expect("output length should be in bounds");
+        hasher.update(bytes);
+
+        let mut hash = vec![0; len];
+        hasher
+            .finalize_variable(&mut hash)
+            .expect("buffer should have the requested output length");
+        f(&hash, options)
+    })
+}
+
+#[cfg(feature = "blake3")]
+fn blake3_impl(tokens: TokenStream, f: impl Encoder) -> TokenStream {
+    input_impl::<XofInput>(tokens, |input, bytes, _, options| {
+        blake3_finalize(blake3::Hasher::new(), input, bytes, options, f)
+    })
+}
+
+#[cfg(feature = "blake3")]
+fn blake3_keyed_impl(tokens: TokenStream, f: impl Encoder) -> TokenStream {
+    input_impl::<KeyedInput<XofInput>>(tokens, |input, bytes, deps, options| {
+        let key = input.key(deps)?;
+        let key = <[u8; blake3::KEY_LEN]>::try_from(key.as_slice()).map_err(|_| {
+            input.key_error(format!(
+                "the key must be {} bytes long, but is {} bytes long",
+                blake3::KEY_LEN,
+                key.len()
+            ))
+        })?;
+
+        blake3_finalize(
+            blake3::Hasher::new_keyed(&key),
+            &input.input,
+            bytes,
+            options,
+            f,
+        )
+    })
+}
+
+#[cfg(feature = "blake3")]
+fn blake3_derive_key_impl(tokens: TokenStream, f: impl Encoder) -> TokenStream {
+    input_impl::<KeyedInput<XofInput>>(tokens, |input, bytes, deps, options| {
+        let context = String::from_utf8(input.key(deps)?)
+            .map_err(|_| input.key_error("the context must be valid UTF-8"))?;
+
+        blake3_finalize(
+            blake3::Hasher::new_derive_key(&context),
+            &input.input,
+            bytes,
+            options,
+            f,
+        )
+    })
+}
+
+#[cfg(feature = "blake3")]
+fn blake3_finalize(
+    mut hasher: blake3::Hasher,
+    input: &XofInput,
+    bytes: &[u8],
+    options: &mut Options,
+    f: impl Encoder,
+) -> syn::Result<TokenStream> {
+    hasher.update(bytes);
+
+    let len = input.bounded_len(input.len_or(blake3::OUT_LEN)?, MAX_XOF_LEN)?;
+    let mut hash = vec![0; len];
+    hasher.finalize_xof().fill(&mut hash);
+    f(&hash, options)
+}
+
+#[cfg(any(
+    feature = "crc",
+    feature = "adler",
+    feature = "xxhash",
+    feature = "fnv"
+))]
+fn checksum_impl(tokens: TokenStream, f: impl FnOnce(&[u8]) -> Literal) -> TokenStream {
+    input_impl::<Input>(tokens, |_, bytes, _, _| {
+        Ok(TokenTree::Literal(f(bytes)).into())
+    })
+}
+
+/// Parses and evaluates the input of a macro, then passes it to `f` along with the options
+fn input_impl<I: Parse + ToBytes>(
+    tokens: TokenStream,
+    f: impl FnOnce(&I, &[u8], &mut Dependencies, &mut Options) -> syn::Result<TokenStream>,
+) -> TokenStream {
+    args_impl::<I>(tokens, |input, deps, options| {
+        let bytes = input.to_bytes(deps)?;
+        f(input, &bytes, deps, options)
+    })
+}
+
+/// Parses the input of a macro and passes it to `f` along with the options
+///
+/// Any dependencies evaluated by `f`, such as keys, are tracked. Options that `f` did not read are
+/// reported as unsupported.
+fn args_impl<I: Parse>(
+    tokens: TokenStream,
+    f: impl FnOnce(&I, &mut Dependencies, &mut Options) -> syn::Result<TokenStream>,
+) -> TokenStream {
+    let Args { input, mut options } = parse_macro_input!(tokens as Args<I>);
+    let mut deps = Dependencies::default();
+    f(&input, &mut deps, &mut options)
+        .and_then(|output| {
+            options.finish()?;
+            deps.track(output)
+        })
+        .unwrap_or_else(|e| e.into_compile_error().into())
 }
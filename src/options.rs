@@ -0,0 +1,203 @@
+use std::fmt::Display;
+
+use syn::parse::{self, Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{bracketed, Ident, Lit, LitInt, LitStr, Token};
+
+/// The input of a macro followed by its options
+pub struct Args<I> {
+    pub input: I,
+    pub options: Options,
+}
+
+impl<I: Parse> Parse for Args<I> {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        let args = input.parse()?;
+        let mut options = Vec::<Opt>::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            let option: Opt = input.parse()?;
+            if options.iter().any(|x| x.name == option.name) {
+                return Err(syn::Error::new(
+                    option.name.span(),
+                    format!("duplicate option `{}`", option.name),
+                ));
+            }
+
+            options.push(option);
+        }
+
+        Ok(Args {
+            input: args,
+            options: Options(options),
+        })
+    }
+}
+
+/// Returns whether the next tokens are a comma followed by an option rather than another literal
+///
+/// Options start with a plain identifier, whereas a macro call has a `!` or `::` after it.
+pub fn peek_option(input: ParseStream) -> bool {
+    input.peek(Token![,])
+        && input.peek2(Ident)
+        && !input.peek3(Token![!])
+        && !input.peek3(Token![::])
+}
+
+/// Options such as `case = upper` given after the input of a macro
+///
+/// Each option is removed as it is read, so anything left over is not supported by the macro.
+pub struct Options(Vec<Opt>);
+
+impl Options {
+    /// Removes an option consisting only of its name, such as `pad`
+    pub fn flag(&mut self, name: &str) -> syn::Result<bool> {
+        match self.take(name) {
+            Some(Opt { value: None, .. }) => Ok(true),
+            Some(Opt { name, .. }) => Err(syn::Error::new(
+                name.span(),
+                format!("`{}` does not take a value", name),
+            )),
+            None => Ok(false),
+        }
+    }
+
+    /// Removes an option whose value is one of the identifiers in `allowed`
+    pub fn keyword<'a>(&mut self, name: &str, allowed: &[&'a str]) -> syn::Result<Option<&'a str>> {
+        let option = match self.take(name) {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+
+        if let Some(Value::Ident(value)) = &option.value {
+            if let Some(x) = allowed.iter().find(|x| value == *x) {
+                return Ok(Some(x));
+            }
+        }
+
+        let allowed = allowed
+            .iter()
+            .map(|x| format!("`{}`", x))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(option.error(format!("expected one of {}", allowed)))
+    }
+
+    /// Removes an option whose value is a string literal
+    pub fn string(&mut self, name: &str) -> syn::Result<Option<LitStr>> {
+        match self.take(name) {
+            Some(Opt {
+                value: Some(Value::Lit(Lit::Str(x))),
+                ..
+            }) => Ok(Some(x)),
+            Some(x) => Err(x.error("expected a string literal")),
+            None => Ok(None),
+        }
+    }
+
+    /// Removes an option whose value is either a string literal or a bracketed list of them
+    pub fn strings(&mut self, name: &str) -> syn::Result<Vec<LitStr>> {
+        match self.take(name) {
+            Some(Opt {
+                value: Some(Value::Lit(Lit::Str(x))),
+                ..
+            }) => Ok(vec![x]),
+            Some(Opt {
+                value: Some(Value::List(list)),
+                name,
+            }) => list
+                .into_iter()
+                .map(|x| match x {
+                    Lit::Str(x) => Ok(x),
+                    x => Err(syn::Error::new(
+                        x.span(),
+                        format!("invalid value for `{}`: expected a string literal", name),
+                    )),
+                })
+                .collect(),
+            Some(x) => Err(x.error("expected a string literal or a list of them")),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Removes an option whose value is an integer literal without a type suffix
+    pub fn int(&mut self, name: &str) -> syn::Result<Option<LitInt>> {
+        match self.take(name) {
+            Some(Opt {
+                value: Some(Value::Lit(Lit::Int(x))),
+                ..
+            }) if x.suffix().is_empty() => Ok(Some(x)),
+            Some(x) => Err(x.error("expected an integer literal without a type suffix")),
+            None => Ok(None),
+        }
+    }
+
+    /// Fails if any option has not been read
+    pub fn finish(self) -> syn::Result<()> {
+        match self.0.into_iter().next() {
+            Some(Opt { name, .. }) => Err(syn::Error::new(
+                name.span(),
+                format!("unsupported option `{}`", name),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn take(&mut self, name: &str) -> Option<Opt> {
+        let i = self.0.iter().position(|x| x.name == name)?;
+        Some(self.0.remove(i))
+    }
+}
+
+struct Opt {
+    name: Ident,
+    value: Option<Value>,
+}
+
+impl Opt {
+    fn error(&self, message: impl Display) -> syn::Error {
+        let span = match &self.value {
+            Some(Value::Ident(x)) => x.span(),
+            Some(Value::Lit(x)) => x.span(),
+            Some(Value::List(_)) | None => self.name.span(),
+        };
+
+        syn::Error::new(
+            span,
+            format!("invalid value for `{}`: {}", self.name, message),
+        )
+    }
+}
+
+impl Parse for Opt {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        let name = input.parse()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(if input.peek(Ident) {
+                Value::Ident(input.parse()?)
+            } else if input.peek(syn::token::Bracket) {
+                let content;
+                bracketed!(content in input);
+                let list = Punctuated::<Lit, Token![,]>::parse_terminated(&content)?;
+                Value::List(list.into_iter().collect())
+            } else {
+                Value::Lit(input.parse()?)
+            })
+        } else {
+            None
+        };
+
+        Ok(Opt { name, value })
+    }
+}
+
+enum Value {
+    Ident(Ident),
+    Lit(Lit),
+    List(Vec<Lit>),
+}
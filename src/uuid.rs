@@ -0,0 +1,85 @@
+use syn::parse::{self, Parse, ParseStream};
+use syn::{Ident, LitStr, Token};
+
+use crate::input::{Dependencies, Input, ToBytes};
+
+const NAMESPACES: [(&str, [u8; 16]); 4] = [
+    ("NAMESPACE_DNS", namespace(0x10)),
+    ("NAMESPACE_URL", namespace(0x11)),
+    ("NAMESPACE_OID", namespace(0x12)),
+    ("NAMESPACE_X500", namespace(0x14)),
+];
+
+/// Returns one of the namespaces defined by RFC 4122, which differ only in their first byte
+const fn namespace(first: u8) -> [u8; 16] {
+    [
+        0x6b, 0xa7, 0xb8, first, 0x9d, 0xad, 0x11, 0xd1, 0x80, 0xb4, 0x00, 0xc0, 0x4f, 0xd4, 0x30,
+        0xc8,
+    ]
+}
+
+/// Input of a name-based UUID, with the namespace given as the first argument
+///
+/// The namespace is either the name of a namespace defined by RFC 4122, such as `NAMESPACE_DNS`,
+/// or a string literal containing a UUID. The name is hashed after the bytes of the namespace.
+pub struct UuidInput {
+    namespace: [u8; 16],
+    input: Input,
+}
+
+impl ToBytes for UuidInput {
+    fn to_bytes(&self, deps: &mut Dependencies) -> syn::Result<Vec<u8>> {
+        let mut bytes = self.namespace.to_vec();
+        bytes.extend(self.input.to_bytes(deps)?);
+        Ok(bytes)
+    }
+}
+
+impl Parse for UuidInput {
+    fn parse(input: ParseStream) -> parse::Result<Self> {
+        let namespace = if input.peek(LitStr) {
+            let uuid: LitStr = input.parse()?;
+            parse_uuid(&uuid.value()).ok_or_else(|| {
+                syn::Error::new(
+                    uuid.span(),
+                    "expected a UUID such as \"6ba7b810-9dad-11d1-80b4-00c04fd430c8\"",
+                )
+            })?
+        } else if input.peek(Ident) {
+            let name: Ident = input.parse()?;
+            NAMESPACES
+                .iter()
+                .find(|(x, _)| name == x)
+                .map(|(_, x)| *x)
+                .ok_or_else(|| {
+                    syn::Error::new(
+                        name.span(),
+                        "expected `NAMESPACE_DNS`, `NAMESPACE_URL`, `NAMESPACE_OID` or `NAMESPACE_X500`",
+                    )
+                })?
+        } else {
+            return Err(input.error("expected a namespace, such as `NAMESPACE_DNS`"));
+        };
+
+        input.parse::<Token![,]>()?;
+        Ok(UuidInput {
+            namespace,
+            input: input.parse()?,
+        })
+    }
+}
+
+/// Parses a UUID in its hyphenated form, or as 32 hexadecimal digits without hyphens
+fn parse_uuid(value: &str) -> Option<[u8; 16]> {
+    let digits = match value.len() {
+        36 if [8, 13, 18, 23].iter().all(|i| value.as_bytes()[*i] == b'-') => {
+            value.replace('-', "")
+        }
+        32 => value.to_owned(),
+        _ => return None,
+    };
+
+    let mut uuid = [0; 16];
+    hex::decode_to_slice(digits, &mut uuid).ok()?;
+    Some(uuid)
+}